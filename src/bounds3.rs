@@ -0,0 +1,224 @@
+use crate::point3::Point3;
+use crate::vector3::Vector3;
+use num::Num;
+
+/// An axis-aligned bounding box in 3D space.
+///
+/// A box is described by its two corners, `min` and `max`. Boxes where `min` isn't less than
+/// `max` along some axis, such as the intersection of two disjoint boxes, are empty.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bounds3<T: Num> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Num + Copy + PartialOrd> Bounds3<T> {
+    /// Creates the smallest box containing both of the given points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::bounds3::Bounds3;
+    /// use pbrust::point3::Point3;
+    ///
+    /// let b = Bounds3::new(Point3::new(1, 5, -2), Point3::new(3, 0, 4));
+    ///
+    /// assert_eq!(Point3::new(1, 0, -2), b.min);
+    /// assert_eq!(Point3::new(3, 5, 4), b.max);
+    /// ```
+    pub fn new(p1: Point3<T>, p2: Point3<T>) -> Bounds3<T> {
+        Bounds3 { min: p1.min(p2), max: p1.max(p2) }
+    }
+
+    /// Computes the smallest box containing this box and the given point.
+    pub fn union_point(self, p: Point3<T>) -> Self {
+        return Self { min: self.min.min(p), max: self.max.max(p) };
+    }
+
+    /// Computes the smallest box containing this box and the given box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::bounds3::Bounds3;
+    /// use pbrust::point3::Point3;
+    ///
+    /// let b1 = Bounds3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+    /// let b2 = Bounds3::new(Point3::new(2, -1, 0), Point3::new(3, 0, 1));
+    ///
+    /// assert_eq!(Bounds3::new(Point3::new(0, -1, 0), Point3::new(3, 1, 1)), b1.union_box(b2));
+    /// ```
+    pub fn union_box(self, other: Bounds3<T>) -> Self {
+        return Self { min: self.min.min(other.min), max: self.max.max(other.max) };
+    }
+
+    /// Computes the region covered by both this box and the given box.
+    ///
+    /// The result is empty if the boxes don't overlap.
+    pub fn intersect(self, other: Bounds3<T>) -> Self {
+        return Self { min: self.min.max(other.min), max: self.max.min(other.max) };
+    }
+
+    /// Checks whether this box is empty.
+    ///
+    /// Boxes with zero extent along some axis are empty, even though they contain the points on
+    /// their boundary.
+    pub fn is_empty(self) -> bool {
+        return self.min.x >= self.max.x || self.min.y >= self.max.y || self.min.z >= self.max.z;
+    }
+
+    /// Checks whether the given point is inside this box, including its boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::bounds3::Bounds3;
+    /// use pbrust::point3::Point3;
+    ///
+    /// let b = Bounds3::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+    ///
+    /// assert!(b.contains(Point3::new(1, 2, 0)));
+    /// assert!(!b.contains(Point3::new(1, 3, 0)));
+    /// ```
+    pub fn contains(self, p: Point3<T>) -> bool {
+        return (self.min.x <= p.x && p.x <= self.max.x)
+            && (self.min.y <= p.y && p.y <= self.max.y)
+            && (self.min.z <= p.z && p.z <= self.max.z);
+    }
+
+    /// Computes the vector from the minimum corner of this box to the maximum corner.
+    pub fn diagonal(self) -> Vector3<T> {
+        return self.max - self.min;
+    }
+
+    /// Computes the total area of the six faces of this box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::bounds3::Bounds3;
+    /// use pbrust::point3::Point3;
+    ///
+    /// let b = Bounds3::new(Point3::new(0, 0, 0), Point3::new(1, 2, 3));
+    ///
+    /// assert_eq!(2 * (1*2 + 1*3 + 2*3), b.surface_area());
+    /// ```
+    pub fn surface_area(self) -> T {
+        let d = self.diagonal();
+        let two = T::one() + T::one();
+        return two * (d.x * d.y + d.x * d.z + d.y * d.z);
+    }
+
+    /// Computes the volume of this box.
+    pub fn volume(self) -> T {
+        let d = self.diagonal();
+        return d.x * d.y * d.z;
+    }
+
+    /// Computes the center of this box.
+    ///
+    /// For integer boxes, the center is rounded towards the minimum corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::bounds3::Bounds3;
+    /// use pbrust::point3::Point3;
+    ///
+    /// let b = Bounds3::new(Point3::new(0.0, -1.0, 2.0), Point3::new(1.0, 1.0, 6.0));
+    ///
+    /// assert_eq!(Point3::new(0.5, 0.0, 4.0), b.centroid());
+    /// ```
+    pub fn centroid(self) -> Point3<T> {
+        return self.min + self.diagonal() / (T::one() + T::one());
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_orders_corners() {
+        let b1 = Bounds3::new(Point3::new(1, 2, 3), Point3::new(-1, 5, 0));
+        let b2 = Bounds3::new(Point3::new(-1, 5, 0), Point3::new(1, 2, 3));
+
+        assert_eq!(Point3::new(-1, 2, 0), b1.min);
+        assert_eq!(Point3::new(1, 5, 3), b1.max);
+        assert_eq!(b1, b2);
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn union_point() {
+            let b = Bounds3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+
+            assert_eq!(b, b.union_point(Point3::new(1, 0, 1)));
+
+            let expected = Bounds3::new(Point3::new(0, -2, 0), Point3::new(1, 1, 4));
+            assert_eq!(expected, b.union_point(Point3::new(1, -2, 4)));
+        }
+
+        #[test]
+        fn union_overlapping_boxes() {
+            let b1 = Bounds3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+            let b2 = Bounds3::new(Point3::new(1.0, -1.0, 1.0), Point3::new(3.0, 1.0, 1.5));
+            let expected = Bounds3::new(Point3::new(0.0, -1.0, 0.0), Point3::new(3.0, 2.0, 2.0));
+
+            assert_eq!(expected, b1.union_box(b2));
+            assert_eq!(expected, b2.union_box(b1));
+        }
+
+        #[test]
+        fn intersect_overlapping_boxes() {
+            let b1 = Bounds3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+            let b2 = Bounds3::new(Point3::new(1.0, -1.0, 1.0), Point3::new(3.0, 1.0, 1.5));
+            let expected = Bounds3::new(Point3::new(1.0, 0.0, 1.0), Point3::new(2.0, 1.0, 1.5));
+
+            assert_eq!(expected, b1.intersect(b2));
+            assert!(!b1.intersect(b2).is_empty());
+        }
+
+        #[test]
+        fn intersect_disjoint_boxes() {
+            let b1 = Bounds3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+            let b2 = Bounds3::new(Point3::new(2, 0, 0), Point3::new(3, 1, 1));
+
+            assert!(b1.intersect(b2).is_empty());
+            assert!(b2.intersect(b1).is_empty());
+        }
+
+        #[test]
+        fn is_empty() {
+            let p = Point3::new(1, 2, 3);
+
+            assert!(Bounds3::new(p, p).is_empty());
+            assert!(Bounds3::new(p, Point3::new(2, 3, 3)).is_empty());
+            assert!(!Bounds3::new(p, Point3::new(2, 3, 4)).is_empty());
+        }
+
+        #[test]
+        fn contains() {
+            let b = Bounds3::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 2.0, 0.5));
+
+            assert!(b.contains(Point3::new(0.0, 1.0, 0.25)));
+            assert!(b.contains(b.min));
+            assert!(b.contains(b.max));
+            assert!(!b.contains(Point3::new(0.0, 1.0, 0.75)));
+            assert!(!b.contains(Point3::new(-1.5, 1.0, 0.25)));
+        }
+
+        #[test]
+        fn measurements() {
+            let b = Bounds3::new(Point3::new(-1, 0, 2), Point3::new(1, 3, 6));
+
+            assert_eq!(Vector3::new(2, 3, 4), b.diagonal());
+            assert_eq!(2 * (2 * 3 + 2 * 4 + 3 * 4), b.surface_area());
+            assert_eq!(2 * 3 * 4, b.volume());
+            assert_eq!(Point3::new(0, 1, 4), b.centroid());
+        }
+    }
+}
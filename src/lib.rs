@@ -1 +1,106 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![allow(clippy::needless_return)]
+
+pub mod bounds3;
+pub mod math;
+pub mod matrix4x4;
+pub mod normal3;
+pub mod octahedral_vector;
+pub mod point2;
+pub mod point3;
+pub mod quaternion;
+pub mod ray;
+pub mod transform;
+pub mod vector2;
 pub mod vector3;
+#[cfg(feature = "std")]
+pub mod vector3_buffer;
+pub mod vector4;
+
+/// The floating-point type used for geometry, which is `f32` unless the `float-as-double`
+/// feature is enabled.
+#[cfg(not(feature = "float-as-double"))]
+pub type Float = f32;
+
+/// The floating-point type used for geometry, which is `f32` unless the `float-as-double`
+/// feature is enabled.
+#[cfg(feature = "float-as-double")]
+pub type Float = f64;
+
+/// A vector of `Float` components.
+pub type Vector3f = vector3::Vector3<Float>;
+
+/// A vector of `i32` components.
+pub type Vector3i = vector3::Vector3<i32>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_width() {
+        #[cfg(not(feature = "float-as-double"))]
+        assert_eq!(4, std::mem::size_of::<Float>());
+
+        #[cfg(feature = "float-as-double")]
+        assert_eq!(8, std::mem::size_of::<Float>());
+    }
+
+    #[test]
+    fn float_vector_operations() {
+        let v = Vector3f::new(3.0, 0.0, -4.0);
+        let n = Vector3f::new(0.0, 0.0, 1.0);
+
+        assert_eq!(5.0, v.length());
+        assert_eq!(Vector3f::new(0.6, 0.0, -0.8), v / v.length());
+        assert_eq!(Vector3f::new(3.0, 0.0, 4.0), v.abs());
+        assert_eq!(Vector3f::new(-3.0, 0.0, -4.0), v.reflect(n));
+        assert_eq!(Vector3f::new(0.0, 1.0, 0.0), n.cross(Vector3f::new(1.0, 0.0, 0.0)));
+        assert!(v.refract(n, 1.0).is_some());
+    }
+
+    #[test]
+    fn integer_vector_operations() {
+        let v = Vector3i::new(3, 0, -4);
+
+        assert_eq!(25, v.length_squared());
+        assert_eq!(Vector3i::new(0, -4, 0), v.cross(Vector3i::new(1, 0, 0)));
+        assert_eq!(Some(Vector3f::new(3.0, 0.0, -4.0)), v.cast::<Float>());
+    }
+
+    /// Run with `cargo test --no-default-features`. Tests always link `std`, but the float math
+    /// here comes from `libm`, just as it does in `no_std` builds.
+    #[cfg(not(feature = "std"))]
+    mod no_std {
+        use super::*;
+        use crate::quaternion::Quaternion;
+        use crate::vector3::Vector3;
+        use core::f64::consts::FRAC_PI_2;
+
+        #[test]
+        fn arithmetic() {
+            let v1 = Vector3i::new(1, -2, 3);
+            let v2 = Vector3i::new(4, 0, -1);
+
+            assert_eq!(Vector3i::new(5, -2, 2), v1 + v2);
+            assert_eq!(Vector3i::new(-3, -2, 4), v1 - v2);
+            assert_eq!(1, v1.dot(v2));
+            assert_eq!(Vector3i::new(2, 13, 8), v1.cross(v2));
+        }
+
+        #[test]
+        fn float_math() {
+            let v = Vector3::new(2.0f64, -3.0, 6.0);
+            let z = Vector3::unit_z();
+
+            assert_eq!(7.0, v.length());
+            assert_eq!(Vector3::new(2.0 / 7.0, -3.0 / 7.0, 6.0 / 7.0), v.normalize());
+
+            let rotated = Vector3::new(1.0, 0.0, 0.0).rotate_about_axis(z, FRAC_PI_2);
+            assert!(rotated.abs_diff_eq(Vector3::unit_y(), 1e-15));
+
+            let q = Quaternion::from_axis_angle(z, FRAC_PI_2);
+            assert!((q.dot(q) - 1.0).abs() < 1e-15);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Numeric helpers shared by the geometry types.
+
+use core::cmp::Ordering;
+use core::num::Wrapping;
+use num::complex::Complex;
+use num::rational::Ratio;
+use num::{Integer, Num};
+
+/// Computes `a * b - c * d`.
+///
+/// Floating-point implementations use the error-free transformation from pbrt-v4, which stays
+/// accurate even when the two products nearly cancel. Integer implementations compute the
+/// expression directly.
+///
+/// # Examples
+///
+/// ```
+/// use pbrust::math::DifferenceOfProducts;
+///
+/// assert_eq!(2 * 3 - 4 * 5, i32::difference_of_products(2, 3, 4, 5));
+/// assert_eq!(2.0 * 3.0 - 4.0 * 5.0, f64::difference_of_products(2.0, 3.0, 4.0, 5.0));
+/// ```
+pub trait DifferenceOfProducts: Sized {
+    fn difference_of_products(a: Self, b: Self, c: Self, d: Self) -> Self;
+}
+
+macro_rules! impl_difference_of_products_exact {
+    ($($t:ty),*) => {
+        $(
+            impl DifferenceOfProducts for $t {
+                fn difference_of_products(a: $t, b: $t, c: $t, d: $t) -> $t {
+                    return a * b - c * d;
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_difference_of_products_fma {
+    ($($t:ty),*) => {
+        $(
+            impl DifferenceOfProducts for $t {
+                fn difference_of_products(a: $t, b: $t, c: $t, d: $t) -> $t {
+                    let cd = c * d;
+                    let difference = num::Float::mul_add(a, b, -cd);
+                    let error = num::Float::mul_add(-c, d, cd);
+                    return difference + error;
+                }
+            }
+        )*
+    };
+}
+
+impl_difference_of_products_exact!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_difference_of_products_fma!(f32, f64);
+
+impl<T: Clone + Integer> DifferenceOfProducts for Ratio<T> {
+    fn difference_of_products(a: Self, b: Self, c: Self, d: Self) -> Self {
+        return a * b - c * d;
+    }
+}
+
+impl<T: Clone + Num> DifferenceOfProducts for Complex<T> {
+    fn difference_of_products(a: Self, b: Self, c: Self, d: Self) -> Self {
+        return a * b - c * d;
+    }
+}
+
+impl<T> DifferenceOfProducts for Wrapping<T> where Wrapping<T>: Num {
+    fn difference_of_products(a: Self, b: Self, c: Self, d: Self) -> Self {
+        return a * b - c * d;
+    }
+}
+
+/// Returns the smaller of two values, preferring the value that isn't NaN.
+pub(crate) fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    return match a.partial_cmp(&b) {
+        Some(Ordering::Greater) => b,
+        Some(_) => a,
+        None => if a.partial_cmp(&a).is_none() { b } else { a },
+    };
+}
+
+/// Returns the larger of two values, preferring the value that isn't NaN.
+pub(crate) fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    return match a.partial_cmp(&b) {
+        Some(Ordering::Less) => b,
+        Some(_) => a,
+        None => if a.partial_cmp(&a).is_none() { b } else { a },
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difference_of_products_integers() {
+        assert_eq!(-14, i32::difference_of_products(2, 3, 4, 5));
+        assert_eq!(14, u32::difference_of_products(4, 5, 2, 3));
+    }
+
+    #[test]
+    fn difference_of_products_num_types() {
+        let half = Ratio::new(1, 2);
+        let third = Ratio::new(1, 3);
+        assert_eq!(Ratio::new(1, 12), Ratio::difference_of_products(half, half, third, half));
+
+        let i = Complex::new(0, 1);
+        assert_eq!(Complex::new(-2, 0), Complex::difference_of_products(i, i, i, -i));
+
+        let max = Wrapping(u8::MAX);
+        let one = Wrapping(1);
+        assert_eq!(Wrapping(0), Wrapping::difference_of_products(max, one, max, one));
+        assert_eq!(Wrapping(2), Wrapping::difference_of_products(one, one, max, one));
+    }
+
+    #[test]
+    fn difference_of_products_cancellation() {
+        let a = 1.0 + f32::EPSILON;
+        let expected = (a as f64) * (a as f64) - 1.0;
+
+        assert_eq!(expected as f32, f32::difference_of_products(a, a, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,264 @@
+use core::ops::Mul;
+
+/// A 4x4 matrix of `f64`s, stored in row-major order.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix4x4 {
+    pub m: [[f64; 4]; 4],
+}
+
+impl Matrix4x4 {
+    /// Creates a new matrix from its rows.
+    pub fn new(m: [[f64; 4]; 4]) -> Matrix4x4 {
+        Matrix4x4 { m }
+    }
+
+    /// Creates the identity matrix.
+    pub fn identity() -> Matrix4x4 {
+        return Matrix4x4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+    }
+
+    /// Computes the transpose of this matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::matrix4x4::Matrix4x4;
+    ///
+    /// let m = Matrix4x4::new([
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [0.0, 1.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 1.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    ///
+    /// assert_eq!([2.0, 1.0, 0.0, 0.0], m.transpose().m[1]);
+    /// ```
+    pub fn transpose(&self) -> Matrix4x4 {
+        let mut t = [[0.0; 4]; 4];
+        for (i, row) in t.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.m[j][i];
+            }
+        }
+        return Matrix4x4::new(t);
+    }
+
+    /// Computes the inverse of this matrix, or `None` if this matrix is singular.
+    ///
+    /// This uses Gauss-Jordan elimination with partial pivoting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::matrix4x4::Matrix4x4;
+    ///
+    /// let scale = Matrix4x4::new([
+    ///     [2.0, 0.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 0.5, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    /// let inverse = Matrix4x4::new([
+    ///     [0.5, 0.0, 0.0, 0.0],
+    ///     [0.0, 0.25, 0.0, 0.0],
+    ///     [0.0, 0.0, 2.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    ///
+    /// assert_eq!(Some(inverse), scale.inverse());
+    /// assert_eq!(None, Matrix4x4::new([[0.0; 4]; 4]).inverse());
+    /// ```
+    pub fn inverse(&self) -> Option<Matrix4x4> {
+        let mut a = self.m;
+        let mut inverse = Matrix4x4::identity().m;
+        for column in 0..4 {
+            // Swap the row with the largest remaining entry in this column into place.
+            let mut pivot = column;
+            for row in column + 1..4 {
+                if a[row][column].abs() > a[pivot][column].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][column] == 0.0 {
+                return None;
+            }
+            a.swap(column, pivot);
+            inverse.swap(column, pivot);
+
+            let scale = 1.0 / a[column][column];
+            for j in 0..4 {
+                a[column][j] *= scale;
+                inverse[column][j] *= scale;
+            }
+
+            for row in 0..4 {
+                if row == column {
+                    continue;
+                }
+                let factor = a[row][column];
+                for j in 0..4 {
+                    a[row][j] -= factor * a[column][j];
+                    inverse[row][j] -= factor * inverse[column][j];
+                }
+            }
+        }
+        return Some(Matrix4x4::new(inverse));
+    }
+}
+
+impl Default for Matrix4x4 {
+    fn default() -> Self {
+        return Matrix4x4::identity();
+    }
+}
+
+impl Mul for Matrix4x4 {
+    type Output = Matrix4x4;
+
+    fn mul(self, other: Matrix4x4) -> Matrix4x4 {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        return Matrix4x4::new(m);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation(x: f64, y: f64, z: f64) -> Matrix4x4 {
+        return Matrix4x4::new([
+            [1.0, 0.0, 0.0, x],
+            [0.0, 1.0, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+    }
+
+    fn assert_near(expected: Matrix4x4, actual: Matrix4x4) {
+        for i in 0..4 {
+            for j in 0..4 {
+                let difference = (expected.m[i][j] - actual.m[i][j]).abs();
+                assert!(difference < 1e-12, "expected {:?}, got {:?}", expected, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn default_is_identity() {
+        assert_eq!(Matrix4x4::identity(), Matrix4x4::default());
+    }
+
+    #[test]
+    fn transpose() {
+        let m = Matrix4x4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let expected = Matrix4x4::new([
+            [1.0, 5.0, 9.0, 13.0],
+            [2.0, 6.0, 10.0, 14.0],
+            [3.0, 7.0, 11.0, 15.0],
+            [4.0, 8.0, 12.0, 16.0],
+        ]);
+
+        assert_eq!(expected, m.transpose());
+        assert_eq!(m, m.transpose().transpose());
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn mul() {
+            let m1 = translation(1.0, 2.0, 3.0);
+            let m2 = translation(-4.0, 0.5, 1.0);
+
+            assert_eq!(translation(-3.0, 2.5, 4.0), m1 * m2);
+            assert_eq!(m1, m1 * Matrix4x4::identity());
+            assert_eq!(m1, Matrix4x4::identity() * m1);
+        }
+
+        #[test]
+        fn mul_is_not_commutative() {
+            let m1 = Matrix4x4::new([
+                [0.0, 1.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]);
+            let m2 = Matrix4x4::new([
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]);
+
+            assert_ne!(m1 * m2, m2 * m1);
+        }
+
+        #[test]
+        fn identity_inverse() {
+            assert_eq!(Some(Matrix4x4::identity()), Matrix4x4::identity().inverse());
+        }
+
+        #[test]
+        fn translation_inverse() {
+            assert_eq!(Some(translation(-1.0, 2.0, -3.5)), translation(1.0, -2.0, 3.5).inverse());
+        }
+
+        #[test]
+        fn inverse_needs_pivoting() {
+            // The leading entry is zero, so elimination fails without swapping rows.
+            let m = Matrix4x4::new([
+                [0.0, 2.0, 0.0, 1.0],
+                [1.0, 0.0, 3.0, 0.0],
+                [0.0, 1.0, 1.0, 0.0],
+                [2.0, 0.0, 0.0, 1.0],
+            ]);
+            let inverse = m.inverse().unwrap();
+
+            assert_near(Matrix4x4::identity(), m * inverse);
+            assert_near(Matrix4x4::identity(), inverse * m);
+        }
+
+        #[test]
+        fn mul_inverse_is_identity() {
+            let m = Matrix4x4::new([
+                [2.0, -1.0, 0.5, 3.0],
+                [0.3, 4.0, -2.0, 1.0],
+                [1.5, 0.0, 1.0, -0.5],
+                [0.0, 0.2, 0.0, 1.0],
+            ]);
+            let inverse = m.inverse().unwrap();
+
+            assert_near(Matrix4x4::identity(), m * inverse);
+            assert_near(Matrix4x4::identity(), inverse * m);
+            assert_near(m, inverse.inverse().unwrap());
+        }
+
+        #[test]
+        fn singular_inverse() {
+            let m = Matrix4x4::new([
+                [1.0, 2.0, 3.0, 4.0],
+                [2.0, 4.0, 6.0, 8.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]);
+
+            assert_eq!(None, m.inverse());
+            assert_eq!(None, Matrix4x4::new([[0.0; 4]; 4]).inverse());
+        }
+    }
+}
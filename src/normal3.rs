@@ -0,0 +1,374 @@
+use crate::math::DifferenceOfProducts;
+use crate::vector3::Vector3;
+use derive_more::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num::{Float, Num, Signed};
+
+/// A surface normal in 3D space.
+///
+/// Normals behave like vectors for most arithmetic, but are kept as a separate type because they
+/// are transformed differently than vectors are. Converting between the two is always explicit,
+/// through `From`, so a normal can't be passed where a vector is expected by accident.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Neg)]
+#[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
+pub struct Normal3<T: Num> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Num> Normal3<T> {
+    /// Creates a new normal.
+    pub fn new(x: T, y: T, z: T) -> Normal3<T> {
+        Normal3 { x, y, z }
+    }
+
+    /// Computes the dot product of this normal with the given normal or vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let n = Normal3::new(1, 2, 3);
+    ///
+    /// assert_eq!(1*2 + 2*4 + 3*6, n.dot(Normal3::new(2, 4, 6)));
+    /// assert_eq!(1*2 + 2*4 + 3*6, n.dot(Vector3::new(2, 4, 6)));
+    /// ```
+    pub fn dot<V: VectorOrNormal<T>>(self, other: V) -> T {
+        return Vector3::from(self).dot(other.into());
+    }
+}
+
+impl<T: Num + Copy> Normal3<T> {
+    /// Computes the squared length of this normal.
+    pub fn length_squared(self) -> T {
+        return Vector3::from(self).length_squared();
+    }
+}
+
+impl<T: Float> Normal3<T> {
+    /// Computes the length of this normal.
+    pub fn length(self) -> T {
+        return Vector3::from(self).length();
+    }
+
+    /// Scales this normal to length 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    ///
+    /// assert_eq!(Normal3::new(0.0, -0.6, 0.8), Normal3::new(0.0, -3.0, 4.0).normalize());
+    /// ```
+    pub fn normalize(self) -> Normal3<T> {
+        return self / self.length();
+    }
+}
+
+impl<T: Signed> Normal3<T> {
+    /// Computes the absolute value of this normal.
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+}
+
+impl<T: Signed + Copy> Normal3<T> {
+    /// Computes the absolute value of the dot product of this normal with the given normal or
+    /// vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let n = Normal3::new(0, 0, 2);
+    ///
+    /// assert_eq!(6, n.abs_dot(Vector3::new(1, 0, -3)));
+    /// assert_eq!(6, n.abs_dot(Normal3::new(1, 0, -3)));
+    /// ```
+    pub fn abs_dot<V: VectorOrNormal<T>>(self, other: V) -> T {
+        return self.dot(other).abs();
+    }
+}
+
+impl<T: Signed + Copy + PartialOrd> Normal3<T> {
+    /// Flips this normal, if necessary, so that it lies in the same hemisphere as the given
+    /// normal or vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let n = Normal3::new(0, 0, 1);
+    ///
+    /// assert_eq!(n, n.face_forward(Vector3::new(1, 0, 1)));
+    /// assert_eq!(-n, n.face_forward(Vector3::new(1, 0, -1)));
+    /// ```
+    pub fn face_forward<V: VectorOrNormal<T>>(self, other: V) -> Normal3<T> {
+        return if self.dot(other) < T::zero() { -self } else { self };
+    }
+}
+
+impl<T: Num + Copy + DifferenceOfProducts> Normal3<T> {
+    /// Computes the cross product of this normal with the given vector.
+    ///
+    /// There is intentionally no cross product of two normals. The result of a cross product
+    /// transforms like a vector only when its inputs are vectors, so crossing two normals is
+    /// almost always a mistake; convert one of them with `Vector3::from` if it's really meant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let n = Normal3::new(0, 0, 1);
+    ///
+    /// assert_eq!(Vector3::new(0, 1, 0), n.cross(Vector3::new(1, 0, 0)));
+    /// ```
+    pub fn cross(self, v: Vector3<T>) -> Vector3<T> {
+        return Vector3::from(self).cross(v);
+    }
+}
+
+impl<T: Num + Copy + DifferenceOfProducts> Vector3<T> {
+    /// Computes the cross product of this vector with the given normal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::normal3::Normal3;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 0, 0);
+    ///
+    /// assert_eq!(Vector3::new(0, -1, 0), v.cross_normal(Normal3::new(0, 0, 1)));
+    /// ```
+    pub fn cross_normal(self, n: Normal3<T>) -> Vector3<T> {
+        return self.cross(Vector3::from(n));
+    }
+}
+
+/// A vector or a normal, which are the types a normal can be dotted with or faced towards.
+///
+/// This is sealed, so other types that convert into a vector, like points, tuples, and arrays,
+/// can't be passed to those methods by accident:
+///
+/// ```compile_fail
+/// use pbrust::normal3::Normal3;
+/// use pbrust::point3::Point3;
+///
+/// let n = Normal3::new(0.0, 0.0, 1.0).face_forward(Point3::new(0.0, 0.0, -1.0));
+/// ```
+///
+/// ```compile_fail
+/// use pbrust::normal3::Normal3;
+///
+/// let d = Normal3::new(0.0, 0.0, 1.0).dot((1.0, 2.0, 3.0));
+/// ```
+pub trait VectorOrNormal<T: Num>: sealed::Sealed + Into<Vector3<T>> {}
+
+impl<T: Num> VectorOrNormal<T> for Vector3<T> {}
+
+impl<T: Num> VectorOrNormal<T> for Normal3<T> {}
+
+mod sealed {
+    use super::*;
+
+    pub trait Sealed {}
+
+    impl<T: Num> Sealed for Vector3<T> {}
+
+    impl<T: Num> Sealed for Normal3<T> {}
+}
+
+impl<T: Num> From<Vector3<T>> for Normal3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Normal3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Num> From<Normal3<T>> for Vector3<T> {
+    fn from(n: Normal3<T>) -> Self {
+        Vector3::new(n.x, n.y, n.z)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_normals_are_equal() {
+        let n1 = Normal3::new(1, 2, 3);
+        let n2 = Normal3::new(1, 2, 3);
+
+        assert_eq!(n1, n2);
+    }
+
+    #[test]
+    fn different_normals_are_not_equal() {
+        let n1 = Normal3::new(1, 2, 3);
+        let n2 = Normal3::new(-3, 0, 5);
+
+        assert_ne!(n1, n2);
+    }
+
+    #[test]
+    fn from_vector() {
+        let v = Vector3::new(1, -2, 3);
+
+        assert_eq!(Normal3::new(1, -2, 3), Normal3::from(v));
+    }
+
+    #[test]
+    fn into_vector() {
+        let n = Normal3::new(1, -2, 3);
+
+        assert_eq!(Vector3::new(1, -2, 3), Vector3::from(n));
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let n = Normal3::new(0.25, -2.0, 3.5);
+        let v = Vector3::new(-1.0, 0.5, 8.0);
+
+        assert_eq!(n, Normal3::from(Vector3::from(n)));
+        assert_eq!(v, Vector3::from(Normal3::from(v)));
+    }
+
+    #[test]
+    fn conversions() {
+        let v = Vector3::new(1, -2, 3);
+        let n: Normal3<i32> = v.into();
+        let u: Vector3<i32> = n.into();
+
+        assert_eq!(Normal3::new(1, -2, 3), n);
+        assert_eq!(v, u);
+        assert_eq!(n, Normal3::from(Vector3::from(n)));
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn add_normals() {
+            let n1 = Normal3::new(1, 2, 3);
+            let n2 = Normal3::new(-3, 0, 5);
+            let expected = Normal3::new(-2, 2, 8);
+
+            assert_eq!(expected, n1 + n2);
+        }
+
+        #[test]
+        fn mul_normal_and_scalar() {
+            let n = Normal3::new(1.0, 2.0, 3.0);
+            let expected = Normal3::new(-2.5, -5.0, -7.5);
+
+            assert_eq!(expected, n * -2.5);
+        }
+
+        #[test]
+        fn dot_product() {
+            let n = Normal3::new(1, -2, -3);
+            let expected = 1 * 2 + -2 * 4 + -3 * -6;
+
+            assert_eq!(expected, n.dot(Normal3::new(2, 4, -6)));
+            assert_eq!(expected, n.dot(Vector3::new(2, 4, -6)));
+        }
+
+        #[test]
+        fn dot_is_symmetric() {
+            let n = Normal3::new(0.5, -2.0, 1.5);
+            let v = Vector3::new(3.0, 0.25, -4.0);
+
+            assert_eq!(n.dot(v), v.dot(Vector3::from(n)));
+            assert_eq!(n.dot(v), Normal3::from(v).dot(n));
+            assert_eq!(n.abs_dot(v), Normal3::from(v).abs_dot(n));
+            assert_eq!(-n.dot(v), n.abs_dot(v));
+        }
+
+        #[test]
+        fn normalize() {
+            let n = Normal3::new(2.0, -3.0, 6.0).normalize();
+
+            assert_eq!(Normal3::new(2.0 / 7.0, -3.0 / 7.0, 6.0 / 7.0), n);
+            assert!((n.length() - 1.0).abs() < 1e-15);
+        }
+
+        #[test]
+        fn abs() {
+            let n = Normal3::new(-1, 2, -3);
+            let expected = Normal3::new(1, 2, 3);
+
+            assert_eq!(expected, n.abs());
+        }
+
+        #[test]
+        fn length() {
+            let n = Normal3::new(2, -3, 6);
+
+            assert_eq!(49, n.length_squared());
+
+            let n = Normal3::new(2.0, -3.0, 6.0);
+
+            assert_eq!(49.0, n.length_squared());
+            assert_eq!(7.0, n.length());
+        }
+
+        #[test]
+        fn face_forward() {
+            let n = Normal3::new(1.0, 2.0, -1.0);
+
+            assert_eq!(n, n.face_forward(Vector3::new(1.0, 0.0, 0.0)));
+            assert_eq!(-n, n.face_forward(Vector3::new(-1.0, 0.0, 0.0)));
+        }
+
+        #[test]
+        fn cross_mixed() {
+            let n = Normal3::new(0.5, -1.0, 2.0);
+            let v = Vector3::new(3.0, 0.25, -4.0);
+            let expected = Vector3::from(n).cross(v);
+
+            assert_eq!(expected, n.cross(v));
+            assert_eq!(-expected, v.cross_normal(n));
+            assert_eq!(0.0, n.dot(n.cross(v)));
+            assert_eq!(0.0, v.dot(v.cross_normal(n)));
+        }
+
+        #[test]
+        fn face_forward_negative_zero_dot() {
+            let n = Normal3::new(1.0, -1.0, 0.0);
+            let v = Vector3::new(-0.0, 0.0, -0.0);
+
+            assert!(n.dot(v).is_sign_negative());
+            assert_eq!(n, n.face_forward(v));
+        }
+
+        #[test]
+        fn face_forward_normal() {
+            let n = Normal3::new(1, 2, -1);
+
+            assert_eq!(n, n.face_forward(Normal3::new(0, 1, 0)));
+            assert_eq!(-n, n.face_forward(Normal3::new(0, 0, 3)));
+        }
+
+        #[test]
+        fn face_forward_perpendicular() {
+            let n = Normal3::new(0, 0, 1);
+
+            assert_eq!(n, n.face_forward(Vector3::new(1, 0, 0)));
+        }
+    }
+}
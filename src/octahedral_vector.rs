@@ -0,0 +1,154 @@
+use crate::vector3::Vector3;
+
+/// A unit vector compactly stored in 32 bits using an octahedral mapping.
+///
+/// The unit sphere is projected onto an octahedron, whose lower half is folded over the upper
+/// half so that the whole octahedron flattens into a square. Each of the square's coordinates is
+/// then quantized to a `u16`. Decoded directions are within about `1e-4` radians of the encoded
+/// ones.
+///
+/// The quantization uses 65535 evenly spaced levels rather than 65536 so that 0 is exactly
+/// representable, which makes the six axis directions round-trip exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct OctahedralVector {
+    x: u16,
+    y: u16,
+}
+
+const MAX_LEVEL: f32 = (u16::MAX - 1) as f32;
+
+impl OctahedralVector {
+    /// Decodes the direction this vector represents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::octahedral_vector::OctahedralVector;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(0.0, -1.0, 0.0);
+    ///
+    /// assert_eq!(v, OctahedralVector::from(v).to_vector3());
+    /// ```
+    pub fn to_vector3(self) -> Vector3<f32> {
+        let x = decode(self.x);
+        let y = decode(self.y);
+        let z = 1.0 - (x.abs() + y.abs());
+        let v = if z < 0.0 {
+            Vector3::new((1.0 - y.abs()) * x.signum(), (1.0 - x.abs()) * y.signum(), z)
+        } else {
+            Vector3::new(x, y, z)
+        };
+        return v / v.length();
+    }
+}
+
+impl From<Vector3<f32>> for OctahedralVector {
+    /// Encodes the direction of the given vector, which doesn't need to be normalized.
+    ///
+    /// The zero vector and vectors with NaN components have no direction, so they are encoded as
+    /// `+z`. Infinite components outweigh finite ones, so only their signs are kept.
+    fn from(v: Vector3<f32>) -> Self {
+        let v = if v.is_finite() || v.is_nan() {
+            v
+        } else {
+            v.map(|c| if c.is_infinite() { c.signum() } else { 0.0 })
+        };
+        let l1_norm = v.x.abs() + v.y.abs() + v.z.abs();
+        if l1_norm.is_nan() || l1_norm == 0.0 {
+            return OctahedralVector { x: encode(0.0), y: encode(0.0) };
+        }
+
+        let v = v / l1_norm;
+        if v.z >= 0.0 {
+            return OctahedralVector { x: encode(v.x), y: encode(v.y) };
+        }
+        return OctahedralVector {
+            x: encode((1.0 - v.y.abs()) * v.x.signum()),
+            y: encode((1.0 - v.x.abs()) * v.y.signum()),
+        };
+    }
+}
+
+impl From<OctahedralVector> for Vector3<f32> {
+    fn from(v: OctahedralVector) -> Self {
+        return v.to_vector3();
+    }
+}
+
+/// Quantizes a value in `[-1, 1]` to the nearest of the evenly spaced levels.
+fn encode(f: f32) -> u16 {
+    return num::Float::round((f.clamp(-1.0, 1.0) + 1.0) / 2.0 * MAX_LEVEL) as u16;
+}
+
+/// Maps a quantized level back to a value in `[-1, 1]`.
+fn decode(level: u16) -> f32 {
+    return level as f32 / MAX_LEVEL * 2.0 - 1.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axes_round_trip_exactly() {
+        let axes = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(-1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, -1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+        ];
+        for &v in &axes {
+            assert_eq!(v, OctahedralVector::from(v).to_vector3());
+            assert_eq!(v, Vector3::from(OctahedralVector::from(v * 3.0)));
+        }
+    }
+
+    #[test]
+    fn directions_round_trip_closely() {
+        // Points on a Fibonacci spiral are spread nearly uniformly over the sphere.
+        let count = 5000;
+        let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+        let mut max_error = 0.0f64;
+        for i in 0..count {
+            let z = 1.0 - (2 * i + 1) as f64 / count as f64;
+            let phi = golden_angle * i as f64;
+            let v = Vector3::spherical_direction((1.0 - z * z).sqrt(), z, phi);
+            let decoded = OctahedralVector::from(v.cast().unwrap()).to_vector3();
+
+            max_error = max_error.max(v.angle_between(decoded.cast().unwrap()));
+        }
+
+        assert!(max_error < 1e-4, "maximum angular error was {}", max_error);
+    }
+
+    #[test]
+    fn directionless_vectors_encode_as_z() {
+        let z = OctahedralVector::from(Vector3::new(0.0, 0.0, 1.0));
+
+        assert_eq!(z, OctahedralVector::from(Vector3::new(0.0, 0.0, 0.0)));
+        assert_eq!(z, OctahedralVector::from(Vector3::new(-0.0, 0.0, -0.0)));
+        assert_eq!(z, OctahedralVector::from(Vector3 { x: 1.0, y: f32::NAN, z: -1.0 }));
+        assert_eq!(Vector3::new(0.0, 0.0, 1.0), z.to_vector3());
+    }
+
+    #[test]
+    fn infinite_components() {
+        let inf = f32::INFINITY;
+        let encoded = OctahedralVector::from(Vector3::new(-inf, 2.0, 0.0));
+        let diagonal = OctahedralVector::from(Vector3::new(1.0, 0.0, -1.0));
+
+        assert_eq!(Vector3::new(-1.0, 0.0, 0.0), encoded.to_vector3());
+        assert_eq!(diagonal, OctahedralVector::from(Vector3::new(inf, 1e30, -inf)));
+    }
+
+    #[test]
+    fn encoding_is_stable() {
+        let v = Vector3::new(0.3f32, -0.5, -0.8);
+        let encoded = OctahedralVector::from(v);
+
+        assert_eq!(encoded, OctahedralVector::from(encoded.to_vector3()));
+    }
+}
@@ -0,0 +1,418 @@
+use crate::math::{partial_max, partial_min};
+use crate::vector2::Vector2;
+use num::{Float, Num, NumCast, PrimInt, ToPrimitive};
+use core::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
+
+/// A position in 2D space, such as a pixel on an image or a position on a camera's film.
+///
+/// As with `Point3`, subtracting two points gives the vector between them, and a vector can be
+/// added to or subtracted from a point to get another point. Adding two points together is
+/// meaningless, so it isn't allowed:
+///
+/// ```compile_fail
+/// use pbrust::point2::Point2;
+///
+/// let p = Point2::new(1, 2) + Point2::new(3, 4);
+/// ```
+///
+/// # Pixel coordinates
+///
+/// Integer points are discrete pixel coordinates and float points are continuous film
+/// coordinates. Following pbrt, the pixel with discrete coordinates `(x, y)` covers the
+/// continuous square `[x, x + 1) × [y, y + 1)` and has its center at `(x + 0.5, y + 0.5)`. So a
+/// continuous position maps to the pixel containing it by rounding down, not to the nearest
+/// integer, and a pixel maps back to its center by adding 0.5. See
+/// [`to_discrete`](Self::to_discrete) and [`to_continuous`](Self::to_continuous).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point2<T: Num> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Num> Point2<T> {
+    /// Creates a new point.
+    pub fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Num + Copy> Point2<T> {
+    /// Linearly interpolates between this point and the given point.
+    ///
+    /// A `t` of 0 gives exactly this point and a `t` of 1 gives exactly the other point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point2::Point2;
+    ///
+    /// let p1 = Point2::new(0.0, 2.0);
+    /// let p2 = Point2::new(1.0, 4.0);
+    ///
+    /// assert_eq!(Point2::new(0.5, 3.0), p1.lerp(p2, 0.5));
+    /// ```
+    pub fn lerp(self, other: Point2<T>, t: T) -> Self {
+        let s = T::one() - t;
+        return Self { x: self.x * s + other.x * t, y: self.y * s + other.y * t };
+    }
+
+    /// Computes the squared distance between this point and the given point.
+    pub fn distance_squared(self, other: Point2<T>) -> T {
+        return (self - other).length_squared();
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Point2<T> {
+    /// Computes the component-wise minimum of this point and the given point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point2::Point2;
+    ///
+    /// let p1 = Point2::new(1, -5);
+    /// let p2 = Point2::new(2, -6);
+    ///
+    /// assert_eq!(Point2::new(1, -6), Point2::min(p1, p2));
+    /// ```
+    pub fn min(self, other: Point2<T>) -> Self {
+        return Self { x: partial_min(self.x, other.x), y: partial_min(self.y, other.y) };
+    }
+
+    /// Computes the component-wise maximum of this point and the given point.
+    pub fn max(self, other: Point2<T>) -> Self {
+        return Self { x: partial_max(self.x, other.x), y: partial_max(self.y, other.y) };
+    }
+}
+
+impl<T: Num + Copy + ToPrimitive> Point2<T> {
+    /// Converts this point to one with a different component type, or `None` if a component
+    /// doesn't fit in `U`.
+    ///
+    /// Like `as`, converting floats to integers truncates towards zero. That isn't the right
+    /// mapping from film positions to pixels, which is what
+    /// [`to_discrete`](Self::to_discrete) is for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point2::Point2;
+    ///
+    /// assert_eq!(Some(Point2::new(1.0, -2.0)), Point2::new(1, -2).cast::<f64>());
+    /// assert_eq!(Some(Point2::new(1, 0)), Point2::new(1.5, -0.5).cast::<i32>());
+    /// assert_eq!(None, Point2::new(-1, 0).cast::<u32>());
+    /// ```
+    pub fn cast<U: Num + NumCast>(self) -> Option<Point2<U>> {
+        return Some(Point2::new(U::from(self.x)?, U::from(self.y)?));
+    }
+}
+
+impl<T: Float> Point2<T> {
+    /// Computes the distance between this point and the given point.
+    pub fn distance(self, other: Point2<T>) -> T {
+        return self.distance_squared(other).sqrt();
+    }
+
+    /// Rounds each component of this point down to an integer.
+    pub fn floor(self) -> Self {
+        return Self { x: self.x.floor(), y: self.y.floor() };
+    }
+
+    /// Rounds each component of this point up to an integer.
+    pub fn ceil(self) -> Self {
+        return Self { x: self.x.ceil(), y: self.y.ceil() };
+    }
+
+    /// Finds the discrete coordinates of the pixel containing this continuous film position.
+    ///
+    /// Each component is rounded down, so everything in `[x, x + 1)` belongs to pixel `x`,
+    /// including negative positions. Returns `None` if a component is NaN or doesn't fit in `I`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point2::Point2;
+    ///
+    /// assert_eq!(Some(Point2::new(2, 0)), Point2::new(2.9, 0.5).to_discrete::<i32>());
+    /// assert_eq!(Some(Point2::new(-1, 3)), Point2::new(-0.1, 3.0).to_discrete::<i32>());
+    /// ```
+    pub fn to_discrete<I: PrimInt>(self) -> Option<Point2<I>> {
+        return self.floor().cast();
+    }
+}
+
+impl<I: PrimInt> Point2<I> {
+    /// Finds the continuous film position of the center of the pixel at these discrete
+    /// coordinates, which is offset by 0.5 in each direction.
+    ///
+    /// Returns `None` if a component can't be represented as a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point2::Point2;
+    ///
+    /// assert_eq!(Some(Point2::new(2.5, -0.5)), Point2::new(2, -1).to_continuous::<f64>());
+    /// ```
+    pub fn to_continuous<T: Float>(self) -> Option<Point2<T>> {
+        let half = T::from(0.5)?;
+        return Some(self.cast::<T>()? + Vector2::new(half, half));
+    }
+}
+
+impl<T: Num> From<Vector2<T>> for Point2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Point2::new(v.x, v.y)
+    }
+}
+
+impl<T: Num> From<Point2<T>> for Vector2<T> {
+    fn from(p: Point2<T>) -> Self {
+        Vector2::new(p.x, p.y)
+    }
+}
+
+impl<T: Num> From<[T; 2]> for Point2<T> {
+    fn from(a: [T; 2]) -> Self {
+        let [x, y] = a;
+        return Point2::new(x, y);
+    }
+}
+
+impl<T: Num> From<Point2<T>> for [T; 2] {
+    fn from(p: Point2<T>) -> Self {
+        return [p.x, p.y];
+    }
+}
+
+impl<T: Num> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        return Point2::new(x, y);
+    }
+}
+
+impl<T: Num> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        return (p.x, p.y);
+    }
+}
+
+impl<T: Num> Index<usize> for Point2<T> {
+    type Output = T;
+
+    /// Gets a component by index, where 0 and 1 correspond to x and y.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 1.
+    fn index(&self, index: usize) -> &T {
+        return match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of bounds for Point2: {}", index),
+        };
+    }
+}
+
+impl<T: Num> IndexMut<usize> for Point2<T> {
+    /// Mutably gets a component by index, where 0 and 1 correspond to x and y.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 1.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        return match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds for Point2: {}", index),
+        };
+    }
+}
+
+impl<T: Num> Sub for Point2<T> {
+    type Output = Vector2<T>;
+
+    fn sub(self, other: Point2<T>) -> Vector2<T> {
+        return Vector2::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+impl<T: Num> Add<Vector2<T>> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, other: Vector2<T>) -> Point2<T> {
+        return Point2::new(self.x + other.x, self.y + other.y);
+    }
+}
+
+impl<T: Num> Sub<Vector2<T>> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, other: Vector2<T>) -> Point2<T> {
+        return Point2::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+impl<T: Num + Copy> AddAssign<Vector2<T>> for Point2<T> {
+    fn add_assign(&mut self, other: Vector2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Num + Copy> SubAssign<Vector2<T>> for Point2<T> {
+    fn sub_assign(&mut self, other: Vector2<T>) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let p = Point2::new(1, -2);
+
+        assert_eq!(p, Point2::from([1, -2]));
+        assert_eq!(p, Point2::from((1, -2)));
+        assert_eq!(p, Point2::from(Vector2::new(1, -2)));
+        assert_eq!([1, -2], <[i32; 2]>::from(p));
+        assert_eq!((1, -2), <(i32, i32)>::from(p));
+        assert_eq!(Vector2::new(1, -2), Vector2::from(p));
+    }
+
+    #[test]
+    fn index() {
+        let mut p = Point2::new(1, 2);
+        p[1] = 5;
+
+        assert_eq!(1, p[0]);
+        assert_eq!(5, p[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Point2: 2")]
+    fn index_out_of_bounds() {
+        let _ = Point2::new(1, 2)[2];
+    }
+
+    #[test]
+    fn cast() {
+        assert_eq!(Some(Point2::new(3.0f32, -4.0)), Point2::new(3i64, -4).cast());
+        assert_eq!(Some(Point2::new(1, -1)), Point2::new(1.9, -1.9).cast::<i32>());
+        assert_eq!(None, Point2::new(f64::NAN, 0.0).cast::<i32>());
+        assert_eq!(None, Point2::new(1e10, 0.0).cast::<i32>());
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn affine_ops() {
+            let p1 = Point2::new(1, 2);
+            let p2 = Point2::new(4, -2);
+            let v = Vector2::new(-3, 5);
+
+            assert_eq!(Vector2::new(3, -4), p2 - p1);
+            assert_eq!(Point2::new(-2, 7), p1 + v);
+            assert_eq!(Point2::new(4, -3), p1 - v);
+            assert_eq!(p2, p1 + (p2 - p1));
+
+            let mut p = p1;
+            p += v;
+            assert_eq!(p1 + v, p);
+            p -= v;
+            assert_eq!(p1, p);
+        }
+
+        #[test]
+        fn distance() {
+            assert_eq!(25, Point2::new(1, 2).distance_squared(Point2::new(4, -2)));
+
+            let p1 = Point2::new(1.0, 2.0);
+            let p2 = Point2::new(4.0, -2.0);
+
+            assert_eq!(25.0, p1.distance_squared(p2));
+            assert_eq!(5.0, p1.distance(p2));
+            assert_eq!(p1.distance(p2), p2.distance(p1));
+        }
+
+        #[test]
+        fn lerp() {
+            let p1 = Point2::new(0.1, -2.0);
+            let p2 = Point2::new(1.7, 4.3);
+
+            assert_eq!(p1, p1.lerp(p2, 0.0));
+            assert_eq!(p2, p1.lerp(p2, 1.0));
+
+            let p1 = Point2::new(0.5, -2.0);
+            let p2 = Point2::new(1.5, 4.0);
+
+            assert_eq!(Point2::new(1.0, 1.0), p1.lerp(p2, 0.5));
+        }
+
+        #[test]
+        fn min_max() {
+            let p1 = Point2::new(1.0, -5.0);
+            let p2 = Point2::new(2.0, -6.0);
+
+            assert_eq!(Point2::new(1.0, -6.0), p1.min(p2));
+            assert_eq!(Point2::new(2.0, -5.0), p1.max(p2));
+        }
+
+        #[test]
+        fn floor_ceil() {
+            let p = Point2::new(1.5, -2.5);
+
+            assert_eq!(Point2::new(1.0, -3.0), p.floor());
+            assert_eq!(Point2::new(2.0, -2.0), p.ceil());
+        }
+    }
+
+    /// Pins the pbrt convention that pixel `(x, y)` covers `[x, x + 1) × [y, y + 1)`.
+    mod pixels {
+        use super::*;
+
+        #[test]
+        fn to_discrete_rounds_down() {
+            let to_discrete = |x, y| Point2::new(x, y).to_discrete::<i32>().unwrap();
+
+            assert_eq!(Point2::new(0, 0), to_discrete(0.0, 0.0));
+            assert_eq!(Point2::new(0, 0), to_discrete(0.5, 0.5));
+            assert_eq!(Point2::new(0, 0), to_discrete(0.999, 0.999));
+            assert_eq!(Point2::new(1, 1), to_discrete(1.0, 1.0));
+            // Rounding to nearest would give (1, 1), and truncating would give (0, 0).
+            assert_eq!(Point2::new(0, -1), to_discrete(0.75, -0.25));
+            assert_eq!(Point2::new(-2, -1), to_discrete(-1.5, -0.5));
+        }
+
+        #[test]
+        fn to_continuous_is_pixel_center() {
+            assert_eq!(Some(Point2::new(0.5, 0.5)), Point2::new(0, 0).to_continuous());
+            assert_eq!(Some(Point2::new(3.5, -1.5)), Point2::new(3, -2).to_continuous());
+            assert_eq!(Some(Point2::new(3.5f32, -1.5)), Point2::new(3i8, -2).to_continuous());
+        }
+
+        #[test]
+        fn round_trip() {
+            for x in -3..3 {
+                for y in -3..3 {
+                    let p = Point2::new(x, y);
+                    let center = p.to_continuous::<f64>().unwrap();
+
+                    assert_eq!(Some(p), center.to_discrete());
+                    // Anywhere within the pixel maps back to it.
+                    assert_eq!(Some(p), (center - Vector2::new(0.5, 0.5)).to_discrete());
+                    assert_eq!(Some(p), (center + Vector2::new(0.49, 0.49)).to_discrete());
+                }
+            }
+        }
+
+        #[test]
+        fn to_discrete_out_of_range() {
+            assert_eq!(None, Point2::new(f64::NAN, 0.0).to_discrete::<i32>());
+            assert_eq!(None, Point2::new(-0.5, 0.0).to_discrete::<u32>());
+            assert_eq!(None, Point2::new(300.0, 0.0).to_discrete::<u8>());
+        }
+    }
+}
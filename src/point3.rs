@@ -0,0 +1,409 @@
+use crate::vector3::Vector3;
+use num::{Float, Num, Signed, ToPrimitive};
+use core::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
+
+/// A position in 3D space.
+///
+/// Points and vectors support different operations: subtracting two points gives the vector
+/// between them, and a vector can be added to or subtracted from a point to get another point.
+/// Adding two points together is meaningless, so it isn't allowed:
+///
+/// ```compile_fail
+/// use pbrust::point3::Point3;
+///
+/// let p = Point3::new(1, 2, 3) + Point3::new(4, 5, 6);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point3<T: Num> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Num> Point3<T> {
+    /// Creates a new point.
+    pub fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Num + Copy> Point3<T> {
+    /// Linearly interpolates between this point and the given point.
+    ///
+    /// A `t` of 0 gives exactly this point and a `t` of 1 gives exactly the other point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    ///
+    /// let p1 = Point3::new(0.0, 2.0, 4.0);
+    /// let p2 = Point3::new(1.0, 4.0, 0.0);
+    ///
+    /// assert_eq!(Point3::new(0.5, 3.0, 2.0), p1.lerp(p2, 0.5));
+    /// ```
+    pub fn lerp(self, other: Point3<T>, t: T) -> Self {
+        let s = T::one() - t;
+        return Self {
+            x: self.x * s + other.x * t,
+            y: self.y * s + other.y * t,
+            z: self.z * s + other.z * t,
+        };
+    }
+
+    /// Rearranges the components of this point.
+    ///
+    /// Each argument is the index of the component of this point to use for the corresponding
+    /// component of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    ///
+    /// assert_eq!(Point3::new(3, 1, 2), Point3::new(1, 2, 3).permute(2, 0, 1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is greater than 2.
+    pub fn permute(self, x: usize, y: usize, z: usize) -> Self {
+        return Self::new(self[x], self[y], self[z]);
+    }
+
+    /// Computes the squared distance between this point and the given point.
+    pub fn distance_squared(self, other: Point3<T>) -> T {
+        return (self - other).length_squared();
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Point3<T> {
+    /// Computes the component-wise minimum of this point and the given point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    ///
+    /// let p1 = Point3::new(1, -5, 3);
+    /// let p2 = Point3::new(2, -4, -6);
+    ///
+    /// assert_eq!(Point3::new(1, -5, -6), Point3::min(p1, p2));
+    /// ```
+    pub fn min(self, other: Point3<T>) -> Self {
+        return Point3::from(Vector3::from(self).min(Vector3::from(other)));
+    }
+
+    /// Computes the component-wise maximum of this point and the given point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    ///
+    /// let p1 = Point3::new(1, -5, 3);
+    /// let p2 = Point3::new(2, -4, -6);
+    ///
+    /// assert_eq!(Point3::new(2, -4, 3), Point3::max(p1, p2));
+    /// ```
+    pub fn max(self, other: Point3<T>) -> Self {
+        return Point3::from(Vector3::from(self).max(Vector3::from(other)));
+    }
+}
+
+impl<T: Num + Copy + ToPrimitive> Point3<T> {
+    /// Computes the distance between this point and the given point as an `f64`.
+    ///
+    /// This works for points of any component type, including integers.
+    pub fn distance_f64(self, other: Point3<T>) -> f64 {
+        return Vector3::from(self).distance_f64(Vector3::from(other));
+    }
+}
+
+impl<T: Float> Point3<T> {
+    /// Computes the distance between this point and the given point.
+    pub fn distance(self, other: Point3<T>) -> T {
+        return (self - other).length();
+    }
+
+    /// Rounds each component of this point down to an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    ///
+    /// assert_eq!(Point3::new(1.0, -3.0, 2.0), Point3::new(1.5, -2.5, 2.0).floor());
+    /// ```
+    pub fn floor(self) -> Self {
+        return Point3::from(Vector3::from(self).floor());
+    }
+
+    /// Rounds each component of this point up to an integer.
+    pub fn ceil(self) -> Self {
+        return Point3::from(Vector3::from(self).ceil());
+    }
+}
+
+impl<T: Signed> Point3<T> {
+    /// Computes the absolute value of each component of this point.
+    pub fn abs(&self) -> Self {
+        return Self { x: self.x.abs(), y: self.y.abs(), z: self.z.abs() };
+    }
+}
+
+impl<T: Num> From<Vector3<T>> for Point3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Num> From<Point3<T>> for Vector3<T> {
+    fn from(p: Point3<T>) -> Self {
+        Vector3::new(p.x, p.y, p.z)
+    }
+}
+
+impl<T: Num> Index<usize> for Point3<T> {
+    type Output = T;
+
+    /// Gets a component by index, where 0, 1, and 2 correspond to x, y, and z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 2.
+    fn index(&self, index: usize) -> &T {
+        return match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds for Point3: {}", index),
+        };
+    }
+}
+
+impl<T: Num> IndexMut<usize> for Point3<T> {
+    /// Mutably gets a component by index, where 0, 1, and 2 correspond to x, y, and z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 2.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        return match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds for Point3: {}", index),
+        };
+    }
+}
+
+impl<T: Num> Sub for Point3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, other: Point3<T>) -> Vector3<T> {
+        return Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z);
+    }
+}
+
+impl<T: Num> Add<Vector3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Vector3<T>) -> Point3<T> {
+        return Point3::new(self.x + other.x, self.y + other.y, self.z + other.z);
+    }
+}
+
+impl<T: Num> Sub<Vector3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Vector3<T>) -> Point3<T> {
+        return Point3::new(self.x - other.x, self.y - other.y, self.z - other.z);
+    }
+}
+
+impl<T: Num + Copy> AddAssign<Vector3<T>> for Point3<T> {
+    fn add_assign(&mut self, other: Vector3<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Num + Copy> SubAssign<Vector3<T>> for Point3<T> {
+    fn sub_assign(&mut self, other: Vector3<T>) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_points_are_equal() {
+        let p1 = Point3::new(1, 2, 3);
+        let p2 = Point3::new(1, 2, 3);
+
+        assert_eq!(p1, p2);
+    }
+
+    #[test]
+    fn different_points_are_not_equal() {
+        let p1 = Point3::new(1, 2, 3);
+        let p2 = Point3::new(-3, 0, 5);
+
+        assert_ne!(p1, p2);
+    }
+
+    #[test]
+    fn from_vector() {
+        let v = Vector3::new(1, -2, 3);
+
+        assert_eq!(Point3::new(1, -2, 3), Point3::from(v));
+    }
+
+    #[test]
+    fn into_vector() {
+        let p = Point3::new(1, -2, 3);
+
+        assert_eq!(Vector3::new(1, -2, 3), Vector3::from(p));
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let p = Point3::new(0.5, -2.0, 3.25);
+        let v: Vector3<f64> = p.into();
+        let q: Point3<f64> = v.into();
+
+        assert_eq!(p, q);
+        assert_eq!(v, Vector3::from(Point3::from(v)));
+    }
+
+    #[test]
+    fn index() {
+        let mut p = Point3::new(1, 2, 3);
+        p[2] = -3;
+
+        assert_eq!((1, 2, -3), (p[0], p[1], p[2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Point3: 3")]
+    fn index_out_of_bounds() {
+        let _ = Point3::new(1, 2, 3)[3];
+    }
+
+    #[test]
+    fn permute() {
+        let p = Point3::new(1.5, -2.0, 3.25);
+
+        assert_eq!(Point3::new(-2.0, 3.25, 1.5), p.permute(1, 2, 0));
+        assert_eq!(Point3::new(1.5, 1.5, 1.5), p.permute(0, 0, 0));
+        assert_eq!(p, p.permute(0, 1, 2));
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn sub_points() {
+            let p1 = Point3::new(1, 2, 3);
+            let p2 = Point3::new(-3, 0, 5);
+            let expected = Vector3::new(4, 2, -2);
+
+            assert_eq!(expected, p1 - p2);
+        }
+
+        #[test]
+        fn add_vector() {
+            let p = Point3::new(1, 2, 3);
+            let v = Vector3::new(-3, 0, 5);
+            let expected = Point3::new(-2, 2, 8);
+
+            assert_eq!(expected, p + v);
+        }
+
+        #[test]
+        fn add_assign_vector() {
+            let mut p = Point3::new(1, 2, 3);
+            let v = Vector3::new(-3, 0, 5);
+            let expected = p + v;
+
+            p += v;
+
+            assert_eq!(expected, p);
+        }
+
+        #[test]
+        fn sub_vector() {
+            let p = Point3::new(1, 2, 3);
+            let v = Vector3::new(-3, 0, 5);
+            let expected = Point3::new(4, 2, -2);
+
+            assert_eq!(expected, p - v);
+        }
+
+        #[test]
+        fn sub_assign_vector() {
+            let mut p = Point3::new(1, 2, 3);
+            let v = Vector3::new(-3, 0, 5);
+            let expected = p - v;
+
+            p -= v;
+
+            assert_eq!(expected, p);
+        }
+
+        #[test]
+        fn distance() {
+            let p1 = Point3::new(1, 2, 3);
+            let p2 = Point3::new(4, -2, 3);
+
+            assert_eq!(25, p1.distance_squared(p2));
+            assert_eq!(5.0, p1.distance_f64(p2));
+
+            let p1 = Point3::new(1.0, 2.0, 3.0);
+            let p2 = Point3::new(4.0, -2.0, 3.0);
+
+            assert_eq!(25.0, p1.distance_squared(p2));
+            assert_eq!(5.0, p1.distance(p2));
+            assert_eq!(p1.distance(p2), p2.distance(p1));
+        }
+
+        #[test]
+        fn min_max() {
+            let p1 = Point3::new(1.0, -5.0, 3.0);
+            let p2 = Point3::new(2.0, -4.0, -6.0);
+
+            assert_eq!(Point3::new(1.0, -5.0, -6.0), p1.min(p2));
+            assert_eq!(Point3::new(2.0, -4.0, 3.0), p1.max(p2));
+        }
+
+        #[test]
+        fn lerp() {
+            let p1 = Point3::new(0.1, -2.0, 7.3);
+            let p2 = Point3::new(1.7, 4.0, -0.9);
+
+            assert_eq!(p1, p1.lerp(p2, 0.0));
+            assert_eq!(p2, p1.lerp(p2, 1.0));
+
+            let p1 = Point3::new(0.5, -2.0, 7.0);
+            let p2 = Point3::new(1.5, 4.0, -1.0);
+
+            assert_eq!(Point3::new(1.0, 1.0, 3.0), p1.lerp(p2, 0.5));
+        }
+
+        #[test]
+        fn floor_ceil() {
+            let p = Point3::new(1.5, -2.5, 3.0);
+
+            assert_eq!(Point3::new(1.0, -3.0, 3.0), p.floor());
+            assert_eq!(Point3::new(2.0, -2.0, 3.0), p.ceil());
+        }
+
+        #[test]
+        fn abs() {
+            assert_eq!(Point3::new(1, 2, 0), Point3::new(-1, 2, 0).abs());
+            assert_eq!(Point3::new(1.5, 0.0, 3.0), Point3::new(-1.5, -0.0, 3.0).abs());
+        }
+    }
+}
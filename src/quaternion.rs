@@ -0,0 +1,311 @@
+use crate::matrix4x4::Matrix4x4;
+use crate::vector3::Vector3;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A quaternion, used to represent rotations in 3D space.
+///
+/// The quaternion is stored as its vector part `v` and its scalar part `w`. Only unit
+/// quaternions represent rotations; `q` and `-q` represent the same rotation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion {
+    pub v: Vector3<f64>,
+    pub w: f64,
+}
+
+impl Quaternion {
+    /// Creates a new quaternion from its vector and scalar parts.
+    pub fn new(v: Vector3<f64>, w: f64) -> Quaternion {
+        Quaternion { v, w }
+    }
+
+    /// Creates the quaternion representing no rotation.
+    pub fn identity() -> Quaternion {
+        return Quaternion::new(Vector3::zero(), 1.0);
+    }
+
+    /// Creates the quaternion that rotates counterclockwise about the given axis by the given
+    /// angle, in radians.
+    ///
+    /// The axis doesn't need to be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::quaternion::Quaternion;
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 2.0), PI);
+    ///
+    /// assert!(q.v.abs_diff_eq(Vector3::new(0.0, 0.0, 1.0), 1e-15));
+    /// assert!(q.w.abs() < 1e-15);
+    /// ```
+    pub fn from_axis_angle(axis: Vector3<f64>, theta: f64) -> Quaternion {
+        let (sin, cos) = num::Float::sin_cos(theta / 2.0);
+        return Quaternion::new(axis.normalize() * sin, cos);
+    }
+
+    /// Computes the dot product of this quaternion with another.
+    pub fn dot(self, other: Quaternion) -> f64 {
+        return self.v.dot(other.v) + self.w * other.w;
+    }
+
+    /// Scales this quaternion to unit length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::quaternion::Quaternion;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let q = Quaternion::new(Vector3::new(0.0, 3.0, 0.0), 4.0).normalize();
+    ///
+    /// assert_eq!(Quaternion::new(Vector3::new(0.0, 0.6, 0.0), 0.8), q);
+    /// ```
+    pub fn normalize(self) -> Quaternion {
+        return self / num::Float::sqrt(self.dot(self));
+    }
+
+    /// Computes the matrix of the rotation this quaternion represents.
+    ///
+    /// This quaternion should be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::matrix4x4::Matrix4x4;
+    /// use pbrust::quaternion::Quaternion;
+    ///
+    /// assert_eq!(Matrix4x4::identity(), Quaternion::identity().to_rotation_matrix());
+    /// ```
+    pub fn to_rotation_matrix(self) -> Matrix4x4 {
+        let Vector3 { x, y, z } = self.v;
+        let w = self.w;
+        return Matrix4x4::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - z * w), 2.0 * (x * z + y * w), 0.0],
+            [2.0 * (x * y + z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - x * w), 0.0],
+            [2.0 * (x * z - y * w), 2.0 * (y * z + x * w), 1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+    }
+
+    /// Spherically interpolates between this rotation and another, with `t` in `[0, 1]`.
+    ///
+    /// The interpolation follows the shorter of the two arcs between the rotations, and falls
+    /// back to normalized linear interpolation when the quaternions are nearly parallel, where
+    /// slerp is numerically unstable. Both quaternions should be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::quaternion::Quaternion;
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::{FRAC_PI_2, PI};
+    ///
+    /// let z = Vector3::new(0.0, 0.0, 1.0);
+    /// let q = Quaternion::identity().slerp(Quaternion::from_axis_angle(z, PI), 0.5);
+    /// let expected = Quaternion::from_axis_angle(z, FRAC_PI_2);
+    ///
+    /// assert!((q.dot(expected) - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn slerp(self, other: Quaternion, t: f64) -> Quaternion {
+        let mut other = other;
+        let mut cos_theta = self.dot(other);
+        if cos_theta < 0.0 {
+            other = -other;
+            cos_theta = -cos_theta;
+        }
+
+        if cos_theta > 0.9995 {
+            return (self * (1.0 - t) + other * t).normalize();
+        }
+
+        let theta = num::Float::acos(cos_theta.min(1.0));
+        let (sin, cos) = num::Float::sin_cos(theta * t);
+        let perpendicular = (other - self * cos_theta).normalize();
+        return self * cos + perpendicular * sin;
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        return Quaternion::identity();
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Quaternion;
+
+    fn add(self, other: Quaternion) -> Quaternion {
+        return Quaternion::new(self.v + other.v, self.w + other.w);
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Quaternion;
+
+    fn sub(self, other: Quaternion) -> Quaternion {
+        return Quaternion::new(self.v - other.v, self.w - other.w);
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+
+    fn neg(self) -> Quaternion {
+        return Quaternion::new(-self.v, -self.w);
+    }
+}
+
+impl Mul<f64> for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, scalar: f64) -> Quaternion {
+        return Quaternion::new(self.v * scalar, self.w * scalar);
+    }
+}
+
+impl Div<f64> for Quaternion {
+    type Output = Quaternion;
+
+    fn div(self, scalar: f64) -> Quaternion {
+        return Quaternion::new(self.v / scalar, self.w / scalar);
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    /// Composes two rotations, so that `q1 * q2` rotates by `q2` and then by `q1`.
+    fn mul(self, other: Quaternion) -> Quaternion {
+        let v = other.v * self.w + self.v * other.w + self.v.cross(other.v);
+        return Quaternion::new(v, self.w * other.w - self.v.dot(other.v));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::Transform;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn rotate(q: Quaternion, v: Vector3<f64>) -> Vector3<f64> {
+        let m = q.to_rotation_matrix();
+        return Transform::new(m, m.transpose()).apply_vector(v);
+    }
+
+    fn assert_same_rotation(expected: Quaternion, actual: Quaternion) {
+        let alignment = expected.dot(actual).abs();
+        assert!((alignment - 1.0).abs() < 1e-12, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn default_is_identity() {
+        assert_eq!(Quaternion::identity(), Quaternion::default());
+    }
+
+    #[test]
+    fn from_axis_angle_is_normalized() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 3.0), 1.2);
+
+        assert!((q.dot(q) - 1.0).abs() < 1e-15);
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn rotation_about_z() {
+            let q = Quaternion::from_axis_angle(Vector3::unit_z(), FRAC_PI_2);
+
+            let v = rotate(q, Vector3::unit_x());
+            assert!(v.abs_diff_eq(Vector3::unit_y(), 1e-15), "got {:?}", v);
+        }
+
+        #[test]
+        fn rotation_matches_transform() {
+            let angle = 0.7;
+            let pairs = [
+                (Vector3::unit_x(), Transform::rotate_x(angle)),
+                (Vector3::unit_y(), Transform::rotate_y(angle)),
+                (Vector3::unit_z(), Transform::rotate_z(angle)),
+            ];
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            for &(axis, t) in &pairs {
+                let q = Quaternion::from_axis_angle(axis, angle);
+
+                assert!(rotate(q, v).abs_diff_eq(t.apply_vector(v), 1e-15));
+            }
+        }
+
+        #[test]
+        fn mul_composes_rotations() {
+            let qx = Quaternion::from_axis_angle(Vector3::unit_x(), FRAC_PI_2);
+            let qz = Quaternion::from_axis_angle(Vector3::unit_z(), FRAC_PI_2);
+
+            // Rotating x about z gives y, and rotating y about x gives z.
+            let v = rotate(qx * qz, Vector3::unit_x());
+            assert!(v.abs_diff_eq(Vector3::unit_z(), 1e-15), "got {:?}", v);
+
+            let q = Quaternion::from_axis_angle(Vector3::unit_y(), 0.4);
+            assert_eq!(q, q * Quaternion::identity());
+            assert_eq!(q, Quaternion::identity() * q);
+        }
+
+        #[test]
+        fn normalize() {
+            let q = Quaternion::new(Vector3::new(1.0, 2.0, -2.0), 4.0).normalize();
+
+            assert!((q.dot(q) - 1.0).abs() < 1e-15);
+            assert_eq!(Quaternion::new(Vector3::new(0.2, 0.4, -0.4), 0.8), q);
+        }
+
+        #[test]
+        fn slerp_endpoints() {
+            let q1 = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0), 0.3);
+            let q2 = Quaternion::from_axis_angle(Vector3::new(0.0, -1.0, 2.0), 2.1);
+
+            assert_same_rotation(q1, q1.slerp(q2, 0.0));
+            assert_same_rotation(q2, q1.slerp(q2, 1.0));
+        }
+
+        #[test]
+        fn slerp_midpoint() {
+            let q1 = Quaternion::from_axis_angle(Vector3::unit_y(), FRAC_PI_4);
+            let q2 = Quaternion::from_axis_angle(Vector3::unit_y(), 3.0 * FRAC_PI_4);
+            let expected = Quaternion::from_axis_angle(Vector3::unit_y(), FRAC_PI_2);
+
+            assert_same_rotation(expected, q1.slerp(q2, 0.5));
+        }
+
+        #[test]
+        fn slerp_takes_short_path() {
+            let q1 = Quaternion::from_axis_angle(Vector3::unit_z(), 0.2);
+            let q2 = -Quaternion::from_axis_angle(Vector3::unit_z(), 0.6);
+
+            let q = q1.slerp(q2, 0.5);
+            assert_same_rotation(Quaternion::from_axis_angle(Vector3::unit_z(), 0.4), q);
+            assert!(q.w > 0.0);
+        }
+
+        #[test]
+        fn slerp_nearly_parallel() {
+            let q1 = Quaternion::from_axis_angle(Vector3::unit_x(), 1.0);
+            let q2 = Quaternion::from_axis_angle(Vector3::unit_x(), 1.0 + 1e-9);
+            let q = q1.slerp(q2, 0.5);
+
+            assert!(!q.v.is_nan() && !q.w.is_nan());
+            assert!((q.dot(q) - 1.0).abs() < 1e-15);
+            assert_same_rotation(q1, q);
+            assert_same_rotation(q1, q1.slerp(q1, 0.3));
+        }
+
+        #[test]
+        fn slerp_opposite_rotations() {
+            let half_turn = Quaternion::from_axis_angle(Vector3::unit_x(), PI);
+            let q = Quaternion::identity().slerp(half_turn, 0.5);
+
+            assert_same_rotation(Quaternion::from_axis_angle(Vector3::unit_x(), FRAC_PI_2), q);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::point3::Point3;
+use crate::vector3::Vector3;
+
+/// A semi-infinite line, starting at an origin and extending in a direction.
+///
+/// Points along the ray are `origin + direction * t` for `t` in `[0, t_max]`. Following pbrt,
+/// the direction isn't required to be normalized, so `t` is measured in multiples of the
+/// direction's length rather than in world-space distance.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray {
+    pub origin: Point3<f64>,
+    pub direction: Vector3<f64>,
+    pub t_max: f64,
+}
+
+impl Ray {
+    /// Creates a new ray that extends infinitely far.
+    pub fn new(origin: Point3<f64>, direction: Vector3<f64>) -> Ray {
+        Ray { origin, direction, t_max: f64::INFINITY }
+    }
+
+    /// Computes the point at the given parametric distance along this ray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    /// use pbrust::ray::Ray;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let r = Ray::new(Point3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0));
+    ///
+    /// assert_eq!(Point3::new(1.0, 3.0, 0.0), r.at(1.5));
+    /// ```
+    pub fn at(&self, t: f64) -> Point3<f64> {
+        return self.origin + self.direction * t;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_is_unbounded() {
+        let r = Ray::new(Point3::new(1.0, 2.0, 3.0), Vector3::new(0.0, 0.0, 1.0));
+
+        assert_eq!(f64::INFINITY, r.t_max);
+    }
+
+    #[test]
+    fn direction_isnt_normalized() {
+        let d = Vector3::new(3.0, 0.0, 4.0);
+        let r = Ray::new(Point3::new(0.0, 0.0, 0.0), d);
+
+        assert_eq!(d, r.direction);
+        assert_eq!(5.0, (r.at(1.0) - r.origin).length());
+    }
+
+    #[test]
+    fn at_zero_is_origin() {
+        let r = Ray::new(Point3::new(1.0, -2.0, 3.0), Vector3::new(0.5, 0.25, -1.0));
+
+        assert_eq!(r.origin, r.at(0.0));
+    }
+
+    #[test]
+    fn at_t_max() {
+        let origin = Point3::new(1.0, -2.0, 3.0);
+        let r = Ray { origin, direction: Vector3::new(0.5, 0.25, -1.0), t_max: 4.0 };
+        let end = r.at(r.t_max);
+
+        assert_eq!(Point3::new(3.0, -1.0, -1.0), end);
+        assert_eq!(r.t_max * r.direction.length(), origin.distance(end));
+    }
+}
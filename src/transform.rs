@@ -0,0 +1,352 @@
+use crate::matrix4x4::Matrix4x4;
+use crate::normal3::Normal3;
+use crate::point3::Point3;
+use crate::vector3::Vector3;
+use core::ops::Mul;
+
+/// An affine or projective transformation of 3D space.
+///
+/// A transform keeps both its matrix and the matrix's inverse, so inverting a transform is free,
+/// and so normals, which are transformed by the inverse transpose, can be transformed without
+/// inverting anything.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    m: Matrix4x4,
+    m_inv: Matrix4x4,
+}
+
+impl Transform {
+    /// Creates a new transform from a matrix and its inverse.
+    ///
+    /// The caller is responsible for `m_inv` actually being the inverse of `m`; use
+    /// `from_matrix` to have it computed instead.
+    pub fn new(m: Matrix4x4, m_inv: Matrix4x4) -> Transform {
+        Transform { m, m_inv }
+    }
+
+    /// Creates a new transform from a matrix, or `None` if the matrix isn't invertible.
+    pub fn from_matrix(m: Matrix4x4) -> Option<Transform> {
+        return Some(Transform::new(m, m.inverse()?));
+    }
+
+    /// Creates the transform that leaves everything unchanged.
+    pub fn identity() -> Transform {
+        return Transform::new(Matrix4x4::identity(), Matrix4x4::identity());
+    }
+
+    /// Creates a transform that moves points by the given offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    /// use pbrust::transform::Transform;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let t = Transform::translate(Vector3::new(1.0, 2.0, 3.0));
+    ///
+    /// assert_eq!(Point3::new(1.0, 3.0, 3.0), t.apply_point(Point3::new(0.0, 1.0, 0.0)));
+    /// ```
+    pub fn translate(delta: Vector3<f64>) -> Transform {
+        let m = Matrix4x4::new([
+            [1.0, 0.0, 0.0, delta.x],
+            [0.0, 1.0, 0.0, delta.y],
+            [0.0, 0.0, 1.0, delta.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let m_inv = Matrix4x4::new([
+            [1.0, 0.0, 0.0, -delta.x],
+            [0.0, 1.0, 0.0, -delta.y],
+            [0.0, 0.0, 1.0, -delta.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Transform::new(m, m_inv);
+    }
+
+    /// Creates a transform that scales each axis by the corresponding component of the given
+    /// vector.
+    pub fn scale(s: Vector3<f64>) -> Transform {
+        let m = Matrix4x4::new([
+            [s.x, 0.0, 0.0, 0.0],
+            [0.0, s.y, 0.0, 0.0],
+            [0.0, 0.0, s.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let m_inv = Matrix4x4::new([
+            [1.0 / s.x, 0.0, 0.0, 0.0],
+            [0.0, 1.0 / s.y, 0.0, 0.0],
+            [0.0, 0.0, 1.0 / s.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Transform::new(m, m_inv);
+    }
+
+    /// Creates a transform that rotates counterclockwise about the x axis by the given angle, in
+    /// radians.
+    pub fn rotate_x(theta: f64) -> Transform {
+        let (sin, cos) = num::Float::sin_cos(theta);
+        let m = Matrix4x4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Transform::new(m, m.transpose());
+    }
+
+    /// Creates a transform that rotates counterclockwise about the y axis by the given angle, in
+    /// radians.
+    pub fn rotate_y(theta: f64) -> Transform {
+        let (sin, cos) = num::Float::sin_cos(theta);
+        let m = Matrix4x4::new([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Transform::new(m, m.transpose());
+    }
+
+    /// Creates a transform that rotates counterclockwise about the z axis by the given angle, in
+    /// radians.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::transform::Transform;
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let t = Transform::rotate_z(FRAC_PI_2);
+    /// let v = t.apply_vector(Vector3::new(1.0, 0.0, 0.0));
+    ///
+    /// assert!(v.abs_diff_eq(Vector3::new(0.0, 1.0, 0.0), 1e-15));
+    /// ```
+    pub fn rotate_z(theta: f64) -> Transform {
+        let (sin, cos) = num::Float::sin_cos(theta);
+        let m = Matrix4x4::new([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Transform::new(m, m.transpose());
+    }
+
+    /// Creates the transform from world space to the space of a camera at `eye` looking at
+    /// `target`.
+    ///
+    /// In camera space, the camera is at the origin looking down +z, with `up` projected onto
+    /// the +y axis. Returns `None` if `eye` and `target` are the same point, or if `up` is
+    /// parallel to the viewing direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::point3::Point3;
+    /// use pbrust::transform::Transform;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let eye = Point3::new(0.0, 0.0, -5.0);
+    /// let target = Point3::new(0.0, 0.0, 0.0);
+    /// let t = Transform::look_at(eye, target, Vector3::new(0.0, 1.0, 0.0)).unwrap();
+    ///
+    /// assert_eq!(Point3::new(0.0, 0.0, 0.0), t.apply_point(eye));
+    /// assert_eq!(Point3::new(0.0, 0.0, 5.0), t.apply_point(target));
+    /// ```
+    pub fn look_at(eye: Point3<f64>, target: Point3<f64>, up: Vector3<f64>) -> Option<Transform> {
+        let dir = (target - eye).try_normalize()?;
+        let right = up.try_normalize()?.cross(dir).try_normalize()?;
+        let new_up = dir.cross(right);
+        let camera_to_world = Matrix4x4::new([
+            [right.x, new_up.x, dir.x, eye.x],
+            [right.y, new_up.y, dir.y, eye.y],
+            [right.z, new_up.z, dir.z, eye.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        return Some(Transform::new(camera_to_world.inverse()?, camera_to_world));
+    }
+
+    /// Gets the matrix of this transform.
+    pub fn matrix(&self) -> &Matrix4x4 {
+        return &self.m;
+    }
+
+    /// Gets the inverse of the matrix of this transform.
+    pub fn inverse_matrix(&self) -> &Matrix4x4 {
+        return &self.m_inv;
+    }
+
+    /// Computes the inverse of this transform.
+    pub fn inverse(&self) -> Transform {
+        return Transform::new(self.m_inv, self.m);
+    }
+
+    /// Applies this transform to a point.
+    ///
+    /// Points are affected by every part of the transform, including translation. For projective
+    /// transforms, the result is divided by its homogeneous coordinate.
+    pub fn apply_point(&self, p: Point3<f64>) -> Point3<f64> {
+        let m = &self.m.m;
+        let x = m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3];
+        let y = m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3];
+        let z = m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3];
+        let w = m[3][0] * p.x + m[3][1] * p.y + m[3][2] * p.z + m[3][3];
+        if w == 1.0 {
+            return Point3::new(x, y, z);
+        }
+        return Point3::new(x / w, y / w, z / w);
+    }
+
+    /// Applies this transform to a vector.
+    ///
+    /// Vectors are directions, so they aren't affected by translation.
+    pub fn apply_vector(&self, v: Vector3<f64>) -> Vector3<f64> {
+        let m = &self.m.m;
+        return Vector3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z);
+    }
+
+    /// Applies this transform to a normal.
+    ///
+    /// Normals are transformed by the transpose of the inverse matrix, which keeps them
+    /// perpendicular to transformed surfaces. The result isn't normalized.
+    pub fn apply_normal(&self, n: Normal3<f64>) -> Normal3<f64> {
+        let m_inv = &self.m_inv.m;
+        return Normal3::new(
+            m_inv[0][0] * n.x + m_inv[1][0] * n.y + m_inv[2][0] * n.z,
+            m_inv[0][1] * n.x + m_inv[1][1] * n.y + m_inv[2][1] * n.z,
+            m_inv[0][2] * n.x + m_inv[1][2] * n.y + m_inv[2][2] * n.z);
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        return Transform::identity();
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+
+    /// Composes two transforms. The result applies `other` first, then `self`.
+    fn mul(self, other: Transform) -> Transform {
+        return Transform::new(self.m * other.m, other.m_inv * self.m_inv);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_points_near(expected: Point3<f64>, actual: Point3<f64>) {
+        assert!(expected.distance(actual) < 1e-12, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn default_is_identity() {
+        let p = Point3::new(1.0, -2.0, 3.0);
+
+        assert_eq!(Transform::identity(), Transform::default());
+        assert_eq!(p, Transform::default().apply_point(p));
+    }
+
+    #[test]
+    fn from_matrix() {
+        let t = Transform::translate(Vector3::new(1.0, 2.0, 3.0));
+
+        assert_eq!(Some(t), Transform::from_matrix(*t.matrix()));
+        assert_eq!(None, Transform::from_matrix(Matrix4x4::new([[0.0; 4]; 4])));
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn translate() {
+            let t = Transform::translate(Vector3::new(1.0, -2.0, 0.5));
+
+            assert_eq!(Point3::new(2.0, -1.0, 1.5), t.apply_point(Point3::new(1.0, 1.0, 1.0)));
+            assert_eq!(Vector3::new(1.0, 1.0, 1.0), t.apply_vector(Vector3::new(1.0, 1.0, 1.0)));
+            assert_eq!(Normal3::new(0.0, 0.0, 1.0), t.apply_normal(Normal3::new(0.0, 0.0, 1.0)));
+        }
+
+        #[test]
+        fn scale() {
+            let t = Transform::scale(Vector3::new(2.0, -1.0, 0.5));
+
+            assert_eq!(Point3::new(2.0, -2.0, 2.0), t.apply_point(Point3::new(1.0, 2.0, 4.0)));
+            assert_eq!(Vector3::new(2.0, -2.0, 2.0), t.apply_vector(Vector3::new(1.0, 2.0, 4.0)));
+        }
+
+        #[test]
+        fn rotate() {
+            let x = Vector3::new(1.0, 0.0, 0.0);
+            let y = Vector3::new(0.0, 1.0, 0.0);
+            let z = Vector3::new(0.0, 0.0, 1.0);
+
+            assert!(Transform::rotate_x(FRAC_PI_2).apply_vector(y).abs_diff_eq(z, 1e-15));
+            assert!(Transform::rotate_y(FRAC_PI_2).apply_vector(z).abs_diff_eq(x, 1e-15));
+            assert!(Transform::rotate_z(FRAC_PI_2).apply_vector(x).abs_diff_eq(y, 1e-15));
+        }
+
+        #[test]
+        fn inverse() {
+            let t = Transform::rotate_y(0.3) * Transform::translate(Vector3::new(1.0, 2.0, 3.0));
+            let p = Point3::new(-1.0, 0.5, 2.0);
+
+            assert_eq!(t, t.inverse().inverse());
+            assert_points_near(p, t.inverse().apply_point(t.apply_point(p)));
+            assert_points_near(p, t.apply_point(t.inverse().apply_point(p)));
+        }
+
+        #[test]
+        fn compose() {
+            let t1 = Transform::translate(Vector3::new(1.0, 0.0, 0.0));
+            let t2 = Transform::rotate_z(FRAC_PI_2);
+            let p = Point3::new(1.0, 0.0, 0.0);
+
+            // Rotating first, then translating.
+            assert_points_near(Point3::new(1.0, 1.0, 0.0), (t1 * t2).apply_point(p));
+            // Translating first, then rotating.
+            assert_points_near(Point3::new(0.0, 2.0, 0.0), (t2 * t1).apply_point(p));
+        }
+
+        #[test]
+        fn normal_stays_perpendicular() {
+            let t = Transform::scale(Vector3::new(4.0, 1.0, 0.5)) * Transform::rotate_x(0.7);
+            let n = Normal3::new(1.0, 1.0, 0.0);
+            let tangent = Vector3::new(1.0, -1.0, 2.0);
+
+            assert_eq!(0.0, n.dot(tangent));
+            assert!(t.apply_normal(n).dot(t.apply_vector(tangent)).abs() < 1e-12);
+            assert!(Vector3::from(t.apply_normal(n)).dot(t.apply_vector(Vector3::from(n))) > 0.0);
+        }
+
+        #[test]
+        fn look_at() {
+            let eye = Point3::new(1.0, 2.0, 3.0);
+            let target = Point3::new(4.0, 6.0, 3.0);
+            let t = Transform::look_at(eye, target, Vector3::new(0.0, 0.0, 1.0)).unwrap();
+
+            let above = eye + Vector3::new(0.0, 0.0, 1.0);
+
+            assert_points_near(Point3::new(0.0, 0.0, 0.0), t.apply_point(eye));
+            assert_points_near(Point3::new(0.0, 0.0, 5.0), t.apply_point(target));
+            assert_points_near(Point3::new(0.0, 1.0, 0.0), t.apply_point(above));
+        }
+
+        #[test]
+        fn look_at_degenerate() {
+            let eye = Point3::new(1.0, 2.0, 3.0);
+            let up = Vector3::new(0.0, 1.0, 0.0);
+
+            assert_eq!(None, Transform::look_at(eye, eye, up));
+            assert_eq!(None, Transform::look_at(eye, eye + up * 2.0, up));
+            assert_eq!(None, Transform::look_at(eye, Point3::new(0.0, 0.0, 0.0), Vector3::zero()));
+        }
+    }
+}
@@ -0,0 +1,468 @@
+use crate::math::{partial_max, partial_min};
+use derive_more::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num::{abs, Float, Num, Signed, ToPrimitive};
+use core::ops::{Index, IndexMut};
+
+/// A vector in 2D space, such as a film position, texture coordinate, or lens sample.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Neg)]
+#[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
+pub struct Vector2<T: Num> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Num> Vector2<T> {
+    /// Creates a new vector.
+    pub fn new(x: T, y: T) -> Vector2<T> {
+        Vector2 { x, y }
+    }
+
+    /// Computes the dot product of this vector with the given vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// let v1 = Vector2::new(1, 2);
+    /// let v2 = Vector2::new(3, 4);
+    /// let expected = 1*3 + 2*4;
+    ///
+    /// assert_eq!(expected, v1.dot(v2));
+    /// ```
+    pub fn dot(self, other: Vector2<T>) -> T {
+        return self.x * other.x + self.y * other.y;
+    }
+}
+
+impl<T: Num + Copy> Vector2<T> {
+    /// Linearly interpolates between this vector and the given vector.
+    ///
+    /// This computes `(1 - t) * self + t * other`, so a `t` of 0 gives exactly this vector and a
+    /// `t` of 1 gives exactly the other vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// let v1 = Vector2::new(0.0, 2.0);
+    /// let v2 = Vector2::new(1.0, 4.0);
+    ///
+    /// assert_eq!(Vector2::new(0.5, 3.0), v1.lerp(v2, 0.5));
+    /// ```
+    pub fn lerp(self, other: Vector2<T>, t: T) -> Self {
+        return Self {
+            x: (T::one() - t) * self.x + t * other.x,
+            y: (T::one() - t) * self.y + t * other.y,
+        };
+    }
+
+    /// Computes the squared length of this vector.
+    ///
+    /// The result is computed in `T`, so it is exact for integer vectors. Like any integer
+    /// arithmetic, it panics on overflow in debug builds and wraps in release builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// assert_eq!(25, Vector2::new(3, -4).length_squared());
+    /// assert_eq!(25.0, Vector2::new(3.0, -4.0).length_squared());
+    /// ```
+    pub fn length_squared(self) -> T {
+        return self.dot(self);
+    }
+}
+
+impl<T: Num + Copy + ToPrimitive> Vector2<T> {
+    /// Computes the length of this vector as an `f64`.
+    ///
+    /// This works for vectors of any component type, including integers. Float vectors can use
+    /// [`length`](Self::length) to get the length in their own type instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// assert_eq!(5.0, Vector2::new(3, -4).length_f64());
+    /// ```
+    pub fn length_f64(self) -> f64 {
+        let to_f64 = |c: T| c.to_f64().expect("Failed to convert to f64!");
+        return Vector2::new(to_f64(self.x), to_f64(self.y)).length();
+    }
+}
+
+impl<T: Float> Vector2<T> {
+    /// Computes the length of this vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// assert_eq!(5.0, Vector2::new(3.0, -4.0).length());
+    /// ```
+    pub fn length(self) -> T {
+        return self.length_squared().sqrt();
+    }
+}
+
+impl<T: Signed> Vector2<T> {
+    /// Computes the absolute value of this vector.
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+
+    /// Computes the absolute value of the dot product of this and another vector.
+    pub fn abs_dot(self, other: Vector2<T>) -> T {
+        return abs(self.dot(other));
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Vector2<T> {
+    /// Computes the component-wise minimum of this vector and the given vector.
+    ///
+    /// NaN components are handled like `f64::min`: if only one of a pair of components is NaN,
+    /// the other component is chosen.
+    pub fn min(self, other: Vector2<T>) -> Self {
+        return Self { x: partial_min(self.x, other.x), y: partial_min(self.y, other.y) };
+    }
+
+    /// Computes the component-wise maximum of this vector and the given vector.
+    ///
+    /// NaN components are handled like `f64::max`: if only one of a pair of components is NaN,
+    /// the other component is chosen.
+    pub fn max(self, other: Vector2<T>) -> Self {
+        return Self { x: partial_max(self.x, other.x), y: partial_max(self.y, other.y) };
+    }
+
+    /// Computes the smallest component of this vector, ignoring NaN components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    ///
+    /// assert_eq!(-3, Vector2::new(2, -3).min_component());
+    /// ```
+    pub fn min_component(self) -> T {
+        return partial_min(self.x, self.y);
+    }
+
+    /// Computes the largest component of this vector, ignoring NaN components.
+    pub fn max_component(self) -> T {
+        return partial_max(self.x, self.y);
+    }
+}
+
+impl<T: Num> Index<usize> for Vector2<T> {
+    type Output = T;
+
+    /// Gets a component by index, where 0 and 1 correspond to x and y.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 1.
+    fn index(&self, index: usize) -> &T {
+        return match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index out of bounds for Vector2: {}", index),
+        };
+    }
+}
+
+impl<T: Num> IndexMut<usize> for Vector2<T> {
+    /// Mutably gets a component by index, where 0 and 1 correspond to x and y.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 1.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        return match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds for Vector2: {}", index),
+        };
+    }
+}
+
+impl<T: Num> From<[T; 2]> for Vector2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Vector2::new(x, y)
+    }
+}
+
+impl<T: Num> From<Vector2<T>> for [T; 2] {
+    fn from(v: Vector2<T>) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl<T: Num> From<(T, T)> for Vector2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vector2::new(x, y)
+    }
+}
+
+impl<T: Num> From<Vector2<T>> for (T, T) {
+    fn from(v: Vector2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_vectors_are_equal() {
+        let v1 = Vector2::new(1, 2);
+        let v2 = Vector2::new(1, 2);
+
+        assert_eq!(v1, v2);
+    }
+
+    #[test]
+    fn different_vectors_are_not_equal() {
+        let v1 = Vector2::new(1, 2);
+        let v2 = Vector2::new(-3, 0);
+
+        assert_ne!(v1, v2);
+    }
+
+    #[test]
+    fn index() {
+        let mut v = Vector2::new(1, 2);
+        v[1] = -2;
+
+        assert_eq!(1, v[0]);
+        assert_eq!(-2, v[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Vector2: 2")]
+    fn index_out_of_bounds() {
+        let _ = Vector2::new(1, 2)[2];
+    }
+
+    #[test]
+    fn conversions() {
+        let v = Vector2::new(1, -2);
+
+        assert_eq!(v, Vector2::from([1, -2]));
+        assert_eq!(v, Vector2::from((1, -2)));
+        assert_eq!([1, -2], <[i32; 2]>::from(v));
+        assert_eq!((1, -2), v.into());
+    }
+
+    #[test]
+    fn hash_map_key() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(Vector2::new(1, 2), "first");
+        map.insert(Vector2::new(2, 1), "second");
+
+        assert_eq!(Some(&"first"), map.get(&Vector2::new(1, 2)));
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn neg() {
+            let v = Vector2::new(-1, 2);
+            let expected = Vector2::new(1, -2);
+
+            assert_eq!(expected, -v);
+        }
+
+        #[test]
+        fn add_vectors() {
+            let v1 = Vector2::new(1, 2);
+            let v2 = Vector2::new(-3, 0);
+            let expected = Vector2::new(-2, 2);
+
+            assert_eq!(expected, v1 + v2);
+        }
+
+        #[test]
+        fn add_assign_vectors() {
+            let mut v1 = Vector2::new(1, 2);
+            let v2 = Vector2::new(-3, 0);
+            let expected = v1 + v2;
+
+            v1 += v2;
+
+            assert_eq!(expected, v1);
+        }
+
+        #[test]
+        fn sub_vectors() {
+            let v1 = Vector2::new(1, 2);
+            let v2 = Vector2::new(-3, 0);
+            let expected = Vector2::new(4, 2);
+
+            assert_eq!(expected, v1 - v2);
+        }
+
+        #[test]
+        fn sub_assign_vectors() {
+            let mut v1 = Vector2::new(1, 2);
+            let v2 = Vector2::new(-3, 0);
+            let expected = Vector2::new(4, 2);
+
+            v1 -= v2;
+
+            assert_eq!(expected, v1);
+        }
+
+        #[test]
+        fn mul_vector_and_scalar() {
+            let v = Vector2::new(1, 2);
+            let expected = Vector2::new(-2, -4);
+
+            assert_eq!(expected, v * -2);
+
+            let v = Vector2::new(1.0, 2.0);
+            let expected = Vector2::new(-2.5, -5.0);
+
+            assert_eq!(expected, v * -2.5);
+        }
+
+        #[test]
+        fn mul_assign_vector_and_scalar() {
+            let mut v = Vector2::new(1, 2);
+            let expected = v * -2;
+
+            v *= -2;
+
+            assert_eq!(expected, v);
+        }
+
+        #[test]
+        fn div_vector_and_scalar() {
+            let v = Vector2::new(-2, -4);
+            let expected = Vector2::new(1, 2);
+
+            assert_eq!(expected, v / -2);
+
+            let v = Vector2::new(-2.5, -5.0);
+            let expected = Vector2::new(1.0, 2.0);
+
+            assert_eq!(expected, v / -2.5);
+        }
+
+        #[test]
+        fn div_assign_vector_and_scalar() {
+            let mut v = Vector2::new(-2, -4);
+            let expected = v / -2;
+
+            v /= -2;
+
+            assert_eq!(expected, v);
+        }
+
+        #[test]
+        fn dot_product() {
+            let v1 = Vector2::new(1, -2);
+            let v2 = Vector2::new(2, 4);
+            let expected = 1 * 2 + -2 * 4;
+
+            assert_eq!(expected, v1.dot(v2));
+        }
+
+        #[test]
+        fn abs() {
+            let v = Vector2::new(-1, 2);
+            let expected = Vector2::new(1, 2);
+
+            assert_eq!(expected, v.abs());
+        }
+
+        #[test]
+        fn length_squared() {
+            let v = Vector2::new(3, -4);
+            let expected = 3 * 3 + -4 * -4;
+
+            assert_eq!(expected, v.length_squared());
+
+            let v = Vector2::new(3.0f32, -4.0);
+
+            assert_eq!(25.0f32, v.length_squared());
+        }
+
+        #[test]
+        fn length_squared_large_integers() {
+            let v = Vector2::new(1i64 << 27, (1 << 27) + 1);
+            let expected = (1i64 << 55) + (1 << 28) + 1;
+
+            assert!(expected > 1 << 53);
+            assert_eq!(expected, v.length_squared());
+        }
+
+        #[test]
+        fn length() {
+            let v = Vector2::new(3.0, -4.0);
+
+            assert_eq!(5.0, v.length());
+
+            let v = Vector2::new(3.0f32, -4.0);
+
+            assert_eq!(5.0f32, v.length());
+        }
+
+        #[test]
+        fn length_f64() {
+            assert_eq!(5.0, Vector2::new(3, -4).length_f64());
+            assert_eq!(5.0, Vector2::new(3u8, 4).length_f64());
+            assert_eq!(5.0, Vector2::new(3.0f32, -4.0).length_f64());
+        }
+
+        #[test]
+        fn lerp() {
+            let v1 = Vector2::new(1.0, -2.0);
+            let v2 = Vector2::new(3.0, 6.0);
+
+            assert_eq!(v1, v1.lerp(v2, 0.0));
+            assert_eq!(v2, v1.lerp(v2, 1.0));
+            assert_eq!(Vector2::new(1.5, 0.0), v1.lerp(v2, 0.25));
+            assert_eq!(Vector2::new(-1.0, -10.0), v1.lerp(v2, -1.0));
+        }
+
+        #[test]
+        fn min_max() {
+            let v1 = Vector2::new(1, -5);
+            let v2 = Vector2::new(2, -6);
+
+            assert_eq!(Vector2::new(1, -6), v1.min(v2));
+            assert_eq!(Vector2::new(2, -5), v1.max(v2));
+        }
+
+        #[test]
+        fn min_max_nan() {
+            let v1 = Vector2 { x: f64::NAN, y: 1.0 };
+            let v2 = Vector2::new(2.0, f64::NAN);
+
+            assert_eq!(Vector2::new(2.0, 1.0), v1.min(v2));
+            assert_eq!(Vector2::new(2.0, 1.0), v1.max(v2));
+            assert_eq!(1.0, v1.min_component());
+            assert_eq!(1.0, v1.max_component());
+        }
+
+        #[test]
+        fn min_max_component() {
+            let v = Vector2::new(3.5, -1.0);
+
+            assert_eq!(-1.0, v.min_component());
+            assert_eq!(3.5, v.max_component());
+        }
+    }
+}
@@ -1,9 +1,58 @@
-use derive_more::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-use num::{abs, Num, Signed, ToPrimitive};
+use crate::math::{partial_max, partial_min, DifferenceOfProducts};
+use crate::vector2::Vector2;
+use derive_more::Neg;
+use num::{abs, Float, Num, NumCast, PrimInt, Signed, ToPrimitive};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
+use core::iter::{FromIterator, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// A vector in 3D space.
+///
+/// Like pbrt, vectors check for NaN components in debug builds: creating a vector with `new`, with
+/// the arithmetic operators, or with methods that compute new components like `cross` panics if
+/// any resulting component is NaN. These checks are compiled out of release builds.
+///
+/// Vectors are `#[repr(C)]`, so they are laid out as the components `x`, `y`, and `z` in order,
+/// with no padding between them.
+///
+/// Vectors of integers are `Eq`, `Hash`, and `Ord`, so they can be used as map keys. They are
+/// ordered lexicographically by `x`, then `y`, then `z`, which is only meant for sorting and
+/// ordered maps. `<` doesn't compare vectors component-wise, so it isn't available for float
+/// vectors, where it would be easy to mistake for a bounds check. Use
+/// [`all_lt`](Self::all_lt), [`any_lt`](Self::any_lt), and their siblings for component-wise
+/// comparisons:
+///
+/// ```
+/// use pbrust::vector3::Vector3;
+///
+/// let v1 = Vector3::new(0, 5, 5);
+/// let v2 = Vector3::new(1, 0, 0);
+///
+/// assert!(v1 < v2);
+/// assert!(!v1.all_lt(v2));
+/// ```
+///
+/// ```compile_fail
+/// use pbrust::vector3::Vector3;
+///
+/// let less = Vector3::new(0.0, 5.0, 5.0) < Vector3::new(1.0, 0.0, 0.0);
+/// ```
+///
+/// Floats aren't `Eq` or `Hash`, so neither are vectors of floats:
+///
+/// ```compile_fail
+/// use pbrust::vector3::Vector3;
+/// use std::collections::HashSet;
+///
+/// let mut set = HashSet::new();
+/// set.insert(Vector3::new(1.0, 2.0, 3.0));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[derive(Neg)]
-#[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
+#[repr(C)]
 pub struct Vector3<T: Num> {
     pub x: T,
     pub y: T,
@@ -12,281 +61,4565 @@ pub struct Vector3<T: Num> {
 
 impl<T: Num> Vector3<T> {
     /// Creates a new vector.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if any component is NaN.
     pub fn new(x: T, y: T, z: T) -> Vector3<T> {
+        debug_assert!(!(is_nan(&x) || is_nan(&y) || is_nan(&z)), "Vector3 has NaN components");
+        Vector3 { x, y, z }
+    }
+
+    /// Creates a new vector in a constant context.
+    ///
+    /// Unlike [`new`](Self::new), this doesn't check for NaN components, which can't be done in
+    /// a `const fn`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// const UP: Vector3<f64> = Vector3::new_const(0.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(Vector3::new(0.0, 2.0, 0.0), UP * 2.0);
+    /// ```
+    pub const fn new_const(x: T, y: T, z: T) -> Vector3<T> {
         Vector3 { x, y, z }
     }
 
-    /// Computes the dot product of this vector with the given vector.
-    ///
-    /// The dot product is calculated by multiplying the corresponding vector fields, then summing
-    /// those products.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use pbrust::vector3::Vector3;
-    ///
-    /// let v1 = Vector3::new(1, 2, 3);
-    /// let v2 = Vector3::new(2, 4, 6);
-    /// let expected = 1*2 + 2*4 + 3*6;
-    ///
-    /// assert_eq!(expected, v1.dot(v2));
-    /// ```
-    pub fn dot(self, other: Vector3<T>) -> T {
-        return self.x * other.x + self.y * other.y + self.z * other.z;
-    }
-}
+    /// Creates the zero vector.
+    pub fn zero() -> Vector3<T> {
+        return Vector3::new(T::zero(), T::zero(), T::zero());
+    }
+
+    /// Creates the vector with all components set to one.
+    pub fn one() -> Vector3<T> {
+        return Vector3::new(T::one(), T::one(), T::one());
+    }
+
+    /// Creates the unit vector along the x axis.
+    pub fn unit_x() -> Vector3<T> {
+        return Vector3::new(T::one(), T::zero(), T::zero());
+    }
+
+    /// Creates the unit vector along the y axis.
+    pub fn unit_y() -> Vector3<T> {
+        return Vector3::new(T::zero(), T::one(), T::zero());
+    }
+
+    /// Creates the unit vector along the z axis.
+    pub fn unit_z() -> Vector3<T> {
+        return Vector3::new(T::zero(), T::zero(), T::one());
+    }
+
+    /// Computes the dot product of this vector with the given vector.
+    ///
+    /// The dot product is calculated by multiplying the corresponding vector fields, then summing
+    /// those products.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(2, 4, 6);
+    /// let expected = 1*2 + 2*4 + 3*6;
+    ///
+    /// assert_eq!(expected, v1.dot(v2));
+    /// ```
+    pub fn dot(self, other: Vector3<T>) -> T {
+        let dot = self.x * other.x + self.y * other.y + self.z * other.z;
+        debug_assert!(!is_nan(&dot), "Dot product is NaN");
+        return dot;
+    }
+
+    /// Returns an iterator over references to the components of this vector, in x, y, z order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 2, 3);
+    ///
+    /// assert_eq!(6, v.iter().sum::<i32>());
+    /// ```
+    pub fn iter(&self) -> core::array::IntoIter<&T, 3> {
+        return IntoIterator::into_iter([&self.x, &self.y, &self.z]);
+    }
+
+    /// Applies a function to each component of this vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 4, 9);
+    ///
+    /// assert_eq!(Vector3::new(1.0, 2.0, 3.0), v.map(|c| (c as f64).sqrt()));
+    /// ```
+    pub fn map<U: Num, F: FnMut(T) -> U>(self, mut f: F) -> Vector3<U> {
+        return Vector3::new(f(self.x), f(self.y), f(self.z));
+    }
+
+    /// Applies a function to each pair of corresponding components of this vector and the given
+    /// vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(2.0, 0.5, -1.0);
+    ///
+    /// assert_eq!(Vector3::new(2.0, 1.0, -3.0), v1.zip_with(v2, |a, b| a as f64 * b));
+    /// ```
+    pub fn zip_with<U: Num, V: Num, F: FnMut(T, U) -> V>(
+        self,
+        other: Vector3<U>,
+        mut f: F,
+    ) -> Vector3<V> {
+        return Vector3::new(f(self.x, other.x), f(self.y, other.y), f(self.z, other.z));
+    }
+}
+
+impl<T: Num + Copy> Vector3<T> {
+    /// Creates a vector with all components set to the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert_eq!(Vector3::new(2, 2, 2), Vector3::splat(2));
+    /// ```
+    pub fn splat(value: T) -> Vector3<T> {
+        return Vector3::new(value, value, value);
+    }
+
+    /// Copies the components of this vector into an array.
+    pub fn as_array(&self) -> [T; 3] {
+        return [self.x, self.y, self.z];
+    }
+
+    /// Rearranges the components of this vector.
+    ///
+    /// Each argument is the index of the component of this vector to use for the corresponding
+    /// component of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 2, 3);
+    ///
+    /// assert_eq!(Vector3::new(3, 1, 2), v.permute(2, 0, 1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is greater than 2.
+    ///
+    /// ```should_panic
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// Vector3::new(1, 2, 3).permute(0, 1, 3);
+    /// ```
+    pub fn permute(self, x: usize, y: usize, z: usize) -> Self {
+        return Self::new(self[x], self[y], self[z]);
+    }
+
+    /// Projects this vector onto the xy plane, giving `(x, y)`.
+    ///
+    /// Together with `xz`, `yz`, and their reversed variants, this is useful for projecting
+    /// onto a coordinate plane, such as when computing texture coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 2, 3);
+    ///
+    /// assert_eq!(Vector2::new(1, 2), v.xy());
+    /// assert_eq!(Vector2::new(3, 1), v.zx());
+    /// ```
+    pub fn xy(self) -> Vector2<T> {
+        return Vector2::new(self.x, self.y);
+    }
+
+    /// Projects this vector onto the xz plane, giving `(x, z)`.
+    pub fn xz(self) -> Vector2<T> {
+        return Vector2::new(self.x, self.z);
+    }
+
+    /// Projects this vector onto the yz plane, giving `(y, z)`.
+    pub fn yz(self) -> Vector2<T> {
+        return Vector2::new(self.y, self.z);
+    }
+
+    /// Projects this vector onto the xy plane with the axes swapped, giving `(y, x)`.
+    pub fn yx(self) -> Vector2<T> {
+        return Vector2::new(self.y, self.x);
+    }
+
+    /// Projects this vector onto the xz plane with the axes swapped, giving `(z, x)`.
+    pub fn zx(self) -> Vector2<T> {
+        return Vector2::new(self.z, self.x);
+    }
+
+    /// Projects this vector onto the yz plane with the axes swapped, giving `(z, y)`.
+    pub fn zy(self) -> Vector2<T> {
+        return Vector2::new(self.z, self.y);
+    }
+
+    /// Copies this vector with its `x` component replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1, 2, 3);
+    ///
+    /// assert_eq!(Vector3::new(1, 2, 0), v.with_z(0).with_x(1));
+    /// assert_eq!(Vector3::new(1, -5, 3), v.with_y(-5));
+    /// ```
+    pub fn with_x(self, x: T) -> Self {
+        return Self { x, ..self };
+    }
+
+    /// Copies this vector with its `y` component replaced.
+    pub fn with_y(self, y: T) -> Self {
+        return Self { y, ..self };
+    }
+
+    /// Copies this vector with its `z` component replaced.
+    pub fn with_z(self, z: T) -> Self {
+        return Self { z, ..self };
+    }
+
+    /// Creates a vector from a 2D vector's `x` and `y` components and the given `z`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector2::Vector2;
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::from_xy(Vector2::new(1, 2), 3);
+    ///
+    /// assert_eq!(Vector3::new(1, 2, 3), v);
+    /// assert_eq!(Vector2::new(1, 2), v.xy());
+    /// ```
+    pub fn from_xy(v: Vector2<T>, z: T) -> Self {
+        return Self::new(v.x, v.y, z);
+    }
+
+    /// Multiplies each component of this vector by the corresponding component of the given
+    /// vector.
+    ///
+    /// This is a method rather than a `Mul` implementation because `*` already scales a vector by
+    /// a scalar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, -5, 6);
+    ///
+    /// assert_eq!(Vector3::new(4, -10, 18), v1.mul_componentwise(v2));
+    /// ```
+    pub fn mul_componentwise(self, other: Vector3<T>) -> Self {
+        return self.zip_with(other, |a, b| a * b);
+    }
+
+    /// Divides each component of this vector by the corresponding component of the given vector.
+    ///
+    /// Integer division truncates toward zero, as it does for `T`.
+    ///
+    /// Dividing by a zero component follows the behavior of `T`: integer vectors panic, while
+    /// float vectors produce infinite or NaN components. NaN components panic in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(4, -10, 18);
+    /// let v2 = Vector3::new(4, -5, 6);
+    ///
+    /// assert_eq!(Vector3::new(1, 2, 3), v1.div_componentwise(v2));
+    /// ```
+    pub fn div_componentwise(self, other: Vector3<T>) -> Self {
+        return self.zip_with(other, |a, b| a / b);
+    }
+
+    /// Divides this vector by the given scalar, or returns `None` if the scalar is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(2, 4, -6);
+    ///
+    /// assert_eq!(Some(Vector3::new(1, 2, -3)), v.checked_div(2));
+    /// assert_eq!(None, v.checked_div(0));
+    /// assert_eq!(None, Vector3::new(1.0, 2.0, 3.0).checked_div(0.0));
+    /// ```
+    pub fn checked_div(self, scalar: T) -> Option<Self> {
+        if scalar.is_zero() {
+            return None;
+        }
+        return Some(self / scalar);
+    }
+
+    /// Divides each component of this vector by the corresponding component of the given vector,
+    /// or returns `None` if any component of the given vector is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(2, 4, -6);
+    /// let v2 = Vector3::new(2, 4, -3);
+    ///
+    /// assert_eq!(Some(Vector3::new(1, 1, 2)), v1.checked_div_componentwise(v2));
+    /// assert_eq!(None, v1.checked_div_componentwise(Vector3::new(2, 0, -3)));
+    /// ```
+    pub fn checked_div_componentwise(self, other: Vector3<T>) -> Option<Self> {
+        if other.x.is_zero() || other.y.is_zero() || other.z.is_zero() {
+            return None;
+        }
+        return Some(self.div_componentwise(other));
+    }
+
+    /// Linearly interpolates between this vector and the given vector.
+    ///
+    /// This computes `(1 - t) * self + t * other`, so a `t` of 0 gives exactly this vector and a
+    /// `t` of 1 gives exactly the other vector. Values of `t` outside of `[0, 1]` extrapolate
+    /// along the line through both vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(0.0, 2.0, 4.0);
+    /// let v2 = Vector3::new(1.0, 4.0, 0.0);
+    ///
+    /// assert_eq!(Vector3::new(0.5, 3.0, 2.0), Vector3::lerp(v1, v2, 0.5));
+    /// ```
+    pub fn lerp(self, other: Vector3<T>, t: T) -> Self {
+        return self.lerp_componentwise(other, Vector3::splat(t));
+    }
+
+    /// Linearly interpolates between this vector and the given vector, using a separate `t` for
+    /// each component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(0.0, 2.0, 4.0);
+    /// let v2 = Vector3::new(1.0, 4.0, 0.0);
+    /// let t = Vector3::new(0.0, 0.5, 1.0);
+    ///
+    /// assert_eq!(Vector3::new(0.0, 3.0, 0.0), v1.lerp_componentwise(v2, t));
+    /// ```
+    pub fn lerp_componentwise(self, other: Vector3<T>, t: Vector3<T>) -> Self {
+        return Self::new(
+            (T::one() - t.x) * self.x + t.x * other.x,
+            (T::one() - t.y) * self.y + t.y * other.y,
+            (T::one() - t.z) * self.z + t.z * other.z);
+    }
+
+    /// Computes the squared length of this vector.
+    ///
+    /// The result is computed in `T`, so it is exact for integer vectors. Like any integer
+    /// arithmetic, it panics on overflow in debug builds and wraps in release builds. Float
+    /// vectors stay in their own precision; convert the result with `to_f64` if a wider type is
+    /// wanted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert_eq!(49, Vector3::new(2, -3, 6).length_squared());
+    /// assert_eq!(49.0, Vector3::new(2.0, -3.0, 6.0).length_squared());
+    /// ```
+    pub fn length_squared(self) -> T {
+        return self.dot(self);
+    }
+
+    /// Computes the squared distance between this vector and the given vector.
+    ///
+    /// Like `length_squared`, this is computed in `T`, so it is exact for integer vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, 6, 3);
+    /// let expected = 3*3 + 4*4 + 0*0;
+    ///
+    /// assert_eq!(expected, v1.distance_squared(v2));
+    /// ```
+    pub fn distance_squared(self, other: Vector3<T>) -> T {
+        return (self - other).length_squared();
+    }
+
+    /// Computes the sum of the given vectors, which is the zero vector for an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let vectors = [Vector3::new(1, 2, 3), Vector3::new(-4, 0, 1)];
+    ///
+    /// assert_eq!(Vector3::new(-3, 2, 4), Vector3::total(&vectors));
+    /// assert_eq!(Vector3::zero(), Vector3::<i32>::total(&[]));
+    /// ```
+    pub fn total(vectors: &[Vector3<T>]) -> Vector3<T> {
+        return vectors.iter().fold(Vector3::zero(), |sum, &v| sum + v);
+    }
+}
+
+impl<T: Num + Copy + DifferenceOfProducts> Vector3<T> {
+    /// Computes the cross product of this vector with the given vector.
+    ///
+    /// Each component is a difference of products, so for floating-point vectors this uses
+    /// [`DifferenceOfProducts`] to stay accurate when the two vectors are nearly parallel.
+    ///
+    /// The component type must implement [`DifferenceOfProducts`]. It's implemented for the
+    /// primitive numbers and for `num`'s `Ratio`, `Complex`, and `Wrapping`; other element types
+    /// can implement it as `a * b - c * d` to get the cross product.
+    pub fn cross(self, other: Vector3<T>) -> Self {
+        return Self::new(
+            T::difference_of_products(self.y, other.z, self.z, other.y),
+            T::difference_of_products(self.z, other.x, self.x, other.z),
+            T::difference_of_products(self.x, other.y, self.y, other.x));
+    }
+
+    /// Computes the scalar triple product `self · (b × c)`.
+    ///
+    /// This is the determinant of the matrix with the three vectors as its rows, which is the
+    /// signed volume of the parallelepiped they span. It is positive when the vectors form a
+    /// right-handed system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let a = Vector3::new(2, 0, 0);
+    /// let b = Vector3::new(0, 3, 0);
+    /// let c = Vector3::new(0, 0, 4);
+    ///
+    /// assert_eq!(24, a.scalar_triple(b, c));
+    /// assert_eq!(-24, a.scalar_triple(c, b));
+    /// ```
+    pub fn scalar_triple(self, b: Vector3<T>, c: Vector3<T>) -> T {
+        return self.dot(b.cross(c));
+    }
+}
+
+impl<T: Num + Copy + ToPrimitive> Vector3<T> {
+    /// Converts this vector to a vector with a different component type.
+    ///
+    /// Float components are truncated towards zero when converted to integers. Returns `None`
+    /// if any component can't be represented in `U`, rather than wrapping or saturating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1i32, 2, 3);
+    ///
+    /// assert_eq!(Some(Vector3::new(1.0, 2.0, 3.0)), v.cast::<f64>());
+    /// assert_eq!(None, Vector3::new(1e300, 0.0, 0.0).cast::<i32>());
+    /// ```
+    pub fn cast<U: Num + NumCast>(self) -> Option<Vector3<U>> {
+        return Some(Vector3::new(U::from(self.x)?, U::from(self.y)?, U::from(self.z)?));
+    }
+
+    /// Computes the average of the given vectors, or `None` for an empty slice.
+    ///
+    /// The sum is accumulated in `f64`, so the centroid of many `f32` or integer vectors doesn't
+    /// lose precision or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let points = [Vector3::new(0, 0, 0), Vector3::new(2, 4, -1)];
+    ///
+    /// assert_eq!(Some(Vector3::new(1.0, 2.0, -0.5)), Vector3::centroid(&points));
+    /// assert_eq!(None, Vector3::<f32>::centroid(&[]));
+    /// ```
+    pub fn centroid(points: &[Vector3<T>]) -> Option<Vector3<f64>> {
+        if points.is_empty() {
+            return None;
+        }
+        let sum: Vector3<f64> = points.iter().map(|p| p.cast().unwrap()).sum();
+        return Some(sum / points.len() as f64);
+    }
+
+    /// Computes the normalized vector.
+    ///
+    /// A normalized vector is the vector with length 1 in the same direction as the original
+    /// vector. The zero vector has no direction, so normalizing it yields a vector of NaNs, which
+    /// panics in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// // Unit vectors are unchanged.
+    /// assert_eq!(Vector3::new(0.0, 1.0, 0.0), Vector3::new(0, 1, 0).normalize());
+    ///
+    /// // Longer vectors are scaled down to length 1.
+    /// assert_eq!(Vector3::new(0.6, 0.0, -0.8), Vector3::new(30, 0, -40).normalize());
+    ///
+    /// // The zero vector can't be normalized; use `try_normalize` instead.
+    /// assert_eq!(None, Vector3::new(0, 0, 0).try_normalize());
+    /// ```
+    pub fn normalize(self) -> Vector3<f64> {
+        let length = self.length_f64();
+        return Vector3::new(
+            self.x.to_f64().unwrap() / length,
+            self.y.to_f64().unwrap() / length,
+            self.z.to_f64().unwrap() / length);
+    }
+
+    /// Computes the normalized vector, or the zero vector if this vector has length 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(0, 0, 0);
+    ///
+    /// assert_eq!(Vector3::new(0.0, 0.0, 0.0), v.normalize_or_zero());
+    /// ```
+    pub fn normalize_or_zero(self) -> Vector3<f64> {
+        if self.length_squared().is_zero() {
+            return Vector3::zero();
+        }
+        return self.normalize();
+    }
+
+    /// Computes the normalized vector, or `None` if this vector has length 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert_eq!(Some(Vector3::new(1.0, 0.0, 0.0)), Vector3::new(5, 0, 0).try_normalize());
+    /// assert_eq!(None, Vector3::new(0, 0, 0).try_normalize());
+    /// ```
+    pub fn try_normalize(self) -> Option<Vector3<f64>> {
+        return self.try_normalize_eps(0.0);
+    }
+
+    /// Computes the normalized vector, or `None` if this vector's length is at most `eps`.
+    ///
+    /// A vector so short that its squared length underflows to zero always gives `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1e-9, 0.0, 0.0);
+    ///
+    /// assert_eq!(Some(Vector3::new(1.0, 0.0, 0.0)), v.try_normalize_eps(1e-12));
+    /// assert_eq!(None, v.try_normalize_eps(1e-6));
+    /// ```
+    pub fn try_normalize_eps(self, eps: f64) -> Option<Vector3<f64>> {
+        if self.length_f64() <= eps {
+            return None;
+        }
+        return Some(self.normalize());
+    }
+
+    /// Computes the normalized vector, or the given fallback if this vector is too short to
+    /// normalize accurately.
+    ///
+    /// Vectors with a length below `1e-12`, including those so short that their squared length
+    /// underflows to zero, give the fallback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let up = Vector3::new(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(Vector3::new(0.6, 0.8, 0.0), Vector3::new(3, 4, 0).normalize_or(up));
+    /// assert_eq!(up, Vector3::new(1e-13, 0.0, 0.0).normalize_or(up));
+    /// ```
+    pub fn normalize_or(self, fallback: Vector3<f64>) -> Vector3<f64> {
+        if self.length_f64() < 1e-12 {
+            return fallback;
+        }
+        return self.normalize();
+    }
+
+    /// Checks whether this vector has length 1, to within `1e-6`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert!(Vector3::new(0.6, 0.0, -0.8).is_normalized());
+    /// assert!(!Vector3::new(1, 1, 0).is_normalized());
+    /// ```
+    pub fn is_normalized(self) -> bool {
+        return (self.length_f64() - 1.0).abs() <= 1e-6;
+    }
+
+    /// Rotates this vector counterclockwise about the given axis by the given angle, in radians.
+    ///
+    /// This uses Rodrigues' rotation formula, which is cheaper than building a `Transform` for a
+    /// single rotation.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `axis` isn't normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let v = Vector3::new(1, 0, 0).rotate_about_axis(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+    ///
+    /// assert!(v.abs_diff_eq(Vector3::new(0.0, 1.0, 0.0), 1e-15));
+    /// ```
+    pub fn rotate_about_axis(self, axis: Vector3<f64>, angle_radians: f64) -> Vector3<f64> {
+        debug_assert!(axis.is_normalized(), "Rotation axis isn't normalized");
+        let v = self.to_f64();
+        let (sin, cos) = angle_radians.sin_cos();
+        return v * cos + axis.cross(v) * sin + axis * (axis.dot(v) * (1.0 - cos));
+    }
+
+    /// Computes the length of this vector as an `f64`.
+    ///
+    /// This works for vectors of any component type, including integers. Float vectors can use
+    /// [`length`](Self::length) to get the length in their own type instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert_eq!(7.0, Vector3::new(2, -3, 6).length_f64());
+    /// ```
+    pub fn length_f64(self) -> f64 {
+        return self.to_f64().length();
+    }
+
+    /// Computes the squared distance between this vector and the given vector as an `f64`.
+    ///
+    /// The components are converted to `f64` before they are subtracted, so unlike
+    /// [`distance_squared`](Self::distance_squared) this can't overflow for integer vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, 6, 3);
+    /// let expected = (3*3 + 4*4 + 0*0) as f64;
+    ///
+    /// assert_eq!(expected, v1.distance_squared_f64(v2));
+    /// ```
+    pub fn distance_squared_f64(self, other: Vector3<T>) -> f64 {
+        return (self.to_f64() - other.to_f64()).length_squared();
+    }
+
+    /// Computes the distance between this vector and the given vector as an `f64`.
+    ///
+    /// This works for vectors of any component type, including integers. Float vectors can use
+    /// [`distance`](Self::distance) to get the distance in their own type instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, 6, 3);
+    /// let expected = ((3*3 + 4*4 + 0*0) as f64).sqrt();
+    ///
+    /// assert_eq!(expected, v1.distance_f64(v2));
+    /// ```
+    pub fn distance_f64(self, other: Vector3<T>) -> f64 {
+        return (self.to_f64() - other.to_f64()).length();
+    }
+
+    /// Converts each component of this vector to an `f64`.
+    fn to_f64(self) -> Vector3<f64> {
+        return self.map(|c| c.to_f64().expect("Failed to convert to f64!"));
+    }
+}
+
+impl<T: Signed> Vector3<T> {
+    /// Computes the absolute value of this vector.
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    /// Computes the absolute value of the dot product of this and another vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(-1, -2, -3);
+    ///
+    /// // absdot is a convenience method:
+    /// let absdot1 = v1.abs_dot(v2);
+    ///
+    /// // It's equivalent to:
+    /// let absdot2 = (v1.dot(v2) as i32).abs();
+    ///
+    /// assert_eq!(absdot1, absdot2);
+    /// ```
+    pub fn abs_dot(self, other: Vector3<T>) -> T {
+        return abs(self.dot(other));
+    }
+}
+
+impl<T: Signed + Copy + PartialOrd> Vector3<T> {
+    /// Flips this vector, if necessary, so that it lies in the same hemisphere as the given
+    /// vector.
+    ///
+    /// This vector is negated if its dot product with the other vector is negative. If the dot
+    /// product is exactly zero, the vectors are perpendicular and this vector is returned
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(0, 1, 1);
+    ///
+    /// // Aligned vectors are unchanged.
+    /// assert_eq!(v, v.face_forward(Vector3::new(0, 2, 0)));
+    ///
+    /// // Opposing vectors are flipped.
+    /// assert_eq!(-v, v.face_forward(Vector3::new(0, -2, 0)));
+    ///
+    /// // Perpendicular vectors are unchanged.
+    /// assert_eq!(v, v.face_forward(Vector3::new(1, 0, 0)));
+    /// ```
+    pub fn face_forward(self, other: Vector3<T>) -> Self {
+        return if self.dot(other) < T::zero() { -self } else { self };
+    }
+
+    /// Computes the Manhattan distance between this vector and the given vector, which is the
+    /// sum of the absolute differences of their components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, -2, 3);
+    ///
+    /// assert_eq!(3 + 4 + 0, v1.manhattan_distance(v2));
+    /// ```
+    pub fn manhattan_distance(self, other: Vector3<T>) -> T {
+        let d = (self - other).abs();
+        return d.x + d.y + d.z;
+    }
+
+    /// Computes the Chebyshev distance between this vector and the given vector, which is the
+    /// largest absolute difference of their components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, 2, 3);
+    /// let v2 = Vector3::new(4, -2, 3);
+    ///
+    /// assert_eq!(4, v1.chebyshev_distance(v2));
+    /// ```
+    pub fn chebyshev_distance(self, other: Vector3<T>) -> T {
+        return (self - other).abs().max_component();
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> Vector3<T> {
+    /// Computes the component-wise minimum of this vector and the given vector.
+    ///
+    /// NaN components are handled like `f64::min`: if only one of a pair of components is NaN,
+    /// the other component is chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, -5, 3);
+    /// let v2 = Vector3::new(2, -4, -6);
+    ///
+    /// assert_eq!(Vector3::new(1, -5, -6), Vector3::min(v1, v2));
+    /// ```
+    pub fn min(self, other: Vector3<T>) -> Self {
+        return Self {
+            x: partial_min(self.x, other.x),
+            y: partial_min(self.y, other.y),
+            z: partial_min(self.z, other.z),
+        };
+    }
+
+    /// Computes the component-wise maximum of this vector and the given vector.
+    ///
+    /// NaN components are handled like `f64::max`: if only one of a pair of components is NaN,
+    /// the other component is chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1, -5, 3);
+    /// let v2 = Vector3::new(2, -4, -6);
+    ///
+    /// assert_eq!(Vector3::new(2, -4, 3), Vector3::max(v1, v2));
+    /// ```
+    pub fn max(self, other: Vector3<T>) -> Self {
+        return Self {
+            x: partial_max(self.x, other.x),
+            y: partial_max(self.y, other.y),
+            z: partial_max(self.z, other.z),
+        };
+    }
+
+    /// Computes the smallest component of this vector, ignoring NaN components.
+    pub fn min_component(self) -> T {
+        return partial_min(self.x, partial_min(self.y, self.z));
+    }
+
+    /// Computes the largest component of this vector, ignoring NaN components.
+    pub fn max_component(self) -> T {
+        return partial_max(self.x, partial_max(self.y, self.z));
+    }
+
+    /// Computes the index of the smallest component of this vector.
+    ///
+    /// Indices 0, 1, and 2 correspond to x, y, and z. If several components are tied for the
+    /// smallest, the later index is returned.
+    pub fn min_dimension(self) -> usize {
+        return if self.x < self.y {
+            if self.x < self.z { 0 } else { 2 }
+        } else if self.y < self.z {
+            1
+        } else {
+            2
+        };
+    }
+
+    /// Computes the index of the largest component of this vector.
+    ///
+    /// Indices 0, 1, and 2 correspond to x, y, and z. If several components are tied for the
+    /// largest, the later index is returned.
+    pub fn max_dimension(self) -> usize {
+        return if self.x > self.y {
+            if self.x > self.z { 0 } else { 2 }
+        } else if self.y > self.z {
+            1
+        } else {
+            2
+        };
+    }
+
+    /// Clamps each component of this vector between the corresponding components of the given
+    /// bounds.
+    ///
+    /// NaN components are left as NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(-2, 5, 1);
+    /// let min = Vector3::new(0, 0, 0);
+    /// let max = Vector3::new(3, 3, 3);
+    ///
+    /// assert_eq!(Vector3::new(0, 3, 1), v.clamp(min, max));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if any component of `min` is greater than the corresponding
+    /// component of `max`. Release builds set such components to the lower bound.
+    pub fn clamp(self, min: Vector3<T>, max: Vector3<T>) -> Self {
+        debug_assert!(
+            (0..3).all(|i| min[i].partial_cmp(&max[i]) != Some(Ordering::Greater)),
+            "Lower bound is greater than upper bound");
+        return Self {
+            x: clamp(self.x, min.x, max.x),
+            y: clamp(self.y, min.y, max.y),
+            z: clamp(self.z, min.z, max.z),
+        };
+    }
+
+    /// Clamps each component of this vector between the given scalar bounds.
+    ///
+    /// This behaves like [`clamp`](Self::clamp) with the same bounds for every component.
+    pub fn clamp_scalar(self, lo: T, hi: T) -> Self {
+        return self.clamp(Vector3::splat(lo), Vector3::splat(hi));
+    }
+
+    /// Computes the component-wise minimum and maximum of the given vectors, or `None` for an
+    /// empty slice.
+    ///
+    /// These are the corners of the smallest axis-aligned box containing every vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let points = [Vector3::new(1, 5, -2), Vector3::new(3, 0, 4), Vector3::new(2, 2, 2)];
+    /// let expected = (Vector3::new(1, 0, -2), Vector3::new(3, 5, 4));
+    ///
+    /// assert_eq!(Some(expected), Vector3::component_bounds(&points));
+    /// ```
+    pub fn component_bounds(points: &[Vector3<T>]) -> Option<(Vector3<T>, Vector3<T>)> {
+        let (&first, rest) = points.split_first()?;
+        return Some(rest.iter().fold((first, first), |(lo, hi), &p| (lo.min(p), hi.max(p))));
+    }
+
+    /// Checks whether every component of this vector is less than the corresponding component
+    /// of the given vector.
+    ///
+    /// Comparisons with NaN are false, so a NaN component in either vector makes this false,
+    /// as it does for the other `all_` predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert!(Vector3::new(1, 2, 3).all_lt(Vector3::new(2, 3, 4)));
+    /// assert!(!Vector3::new(1, 2, 3).all_lt(Vector3::new(2, 2, 4)));
+    ///
+    /// let nan = Vector3 { x: 1.0, y: f64::NAN, z: 3.0 };
+    /// assert!(!nan.all_lt(Vector3::new(2.0, 3.0, 4.0)));
+    /// ```
+    pub fn all_lt(self, other: Vector3<T>) -> bool {
+        return self.x < other.x && self.y < other.y && self.z < other.z;
+    }
+
+    /// Checks whether every component of this vector is less than or equal to the corresponding
+    /// component of the given vector.
+    pub fn all_le(self, other: Vector3<T>) -> bool {
+        return self.x <= other.x && self.y <= other.y && self.z <= other.z;
+    }
+
+    /// Checks whether every component of this vector is greater than the corresponding component
+    /// of the given vector.
+    pub fn all_gt(self, other: Vector3<T>) -> bool {
+        return self.x > other.x && self.y > other.y && self.z > other.z;
+    }
+
+    /// Checks whether every component of this vector is greater than or equal to the
+    /// corresponding component of the given vector.
+    pub fn all_ge(self, other: Vector3<T>) -> bool {
+        return self.x >= other.x && self.y >= other.y && self.z >= other.z;
+    }
+
+    /// Checks whether any component of this vector is less than the corresponding component of
+    /// the given vector.
+    ///
+    /// Comparisons with NaN are false, so pairs of components with a NaN never make this true,
+    /// as for the other `any_` predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert!(Vector3::new(1, 5, 5).any_lt(Vector3::new(2, 3, 4)));
+    /// assert!(!Vector3::new(2, 3, 4).any_lt(Vector3::new(2, 3, 4)));
+    /// ```
+    pub fn any_lt(self, other: Vector3<T>) -> bool {
+        return self.x < other.x || self.y < other.y || self.z < other.z;
+    }
+
+    /// Checks whether any component of this vector is less than or equal to the corresponding
+    /// component of the given vector.
+    pub fn any_le(self, other: Vector3<T>) -> bool {
+        return self.x <= other.x || self.y <= other.y || self.z <= other.z;
+    }
+
+    /// Checks whether any component of this vector is greater than the corresponding component of
+    /// the given vector.
+    pub fn any_gt(self, other: Vector3<T>) -> bool {
+        return self.x > other.x || self.y > other.y || self.z > other.z;
+    }
+
+    /// Checks whether any component of this vector is greater than or equal to the corresponding
+    /// component of the given vector.
+    pub fn any_ge(self, other: Vector3<T>) -> bool {
+        return self.x >= other.x || self.y >= other.y || self.z >= other.z;
+    }
+}
+
+impl<T: Float> Vector3<T> {
+    /// Computes the length of this vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert_eq!(7.0, Vector3::new(2.0, -3.0, 6.0).length());
+    /// ```
+    pub fn length(self) -> T {
+        let length = self.length_squared().sqrt();
+        debug_assert!(!length.is_nan(), "Length is NaN");
+        return length;
+    }
+
+    /// Computes the distance between this vector and the given vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1.0, 2.0, 3.0);
+    /// let v2 = Vector3::new(4.0, 6.0, 3.0);
+    ///
+    /// assert_eq!(5.0, v1.distance(v2));
+    /// ```
+    pub fn distance(self, other: Vector3<T>) -> T {
+        return (self - other).length();
+    }
+
+    /// Checks whether any component of this vector is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert!(Vector3 { x: 1.0, y: f64::NAN, z: 0.0 }.is_nan());
+    /// assert!(!Vector3::new(1.0, f64::INFINITY, 0.0).is_nan());
+    /// ```
+    pub fn is_nan(self) -> bool {
+        return self.x.is_nan() || self.y.is_nan() || self.z.is_nan();
+    }
+
+    /// Checks whether any component of this vector is NaN, like pbrt's `HasNaNs`.
+    ///
+    /// This is the same as [`is_nan`](Self::is_nan).
+    pub fn has_nans(self) -> bool {
+        return self.is_nan();
+    }
+
+    /// Checks whether every component of this vector is finite, meaning neither infinite nor NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// assert!(Vector3::new(1.0, -2.0, 0.0).is_finite());
+    /// assert!(!Vector3::new(1.0, f64::INFINITY, 0.0).is_finite());
+    /// ```
+    pub fn is_finite(self) -> bool {
+        return self.x.is_finite() && self.y.is_finite() && self.z.is_finite();
+    }
+
+    /// Rounds each component of this vector down to the nearest integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.5, -0.5, 2.0);
+    ///
+    /// assert_eq!(Vector3::new(1.0, -1.0, 2.0), v.floor());
+    /// ```
+    pub fn floor(self) -> Self {
+        return Self::new(self.x.floor(), self.y.floor(), self.z.floor());
+    }
+
+    /// Rounds each component of this vector up to the nearest integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.5, -0.5, 2.0);
+    ///
+    /// assert_eq!(Vector3::new(2.0, -0.0, 2.0), v.ceil());
+    /// ```
+    pub fn ceil(self) -> Self {
+        return Self::new(self.x.ceil(), self.y.ceil(), self.z.ceil());
+    }
+
+    /// Rounds each component of this vector to the nearest integer, rounding half-way cases away
+    /// from zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.5, -0.5, 2.2);
+    ///
+    /// assert_eq!(Vector3::new(2.0, -1.0, 2.0), v.round());
+    /// ```
+    pub fn round(self) -> Self {
+        return Self::new(self.x.round(), self.y.round(), self.z.round());
+    }
+
+    /// Rounds each component of this vector towards zero.
+    pub fn trunc(self) -> Self {
+        return Self::new(self.x.trunc(), self.y.trunc(), self.z.trunc());
+    }
+
+    /// Converts this vector to an integer vector, truncating each component towards zero.
+    ///
+    /// Returns `None` if any component is NaN or doesn't fit in `I`. Combine this with
+    /// [`floor`](Self::floor), [`ceil`](Self::ceil), or [`round`](Self::round) to pick a
+    /// different rounding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.5, -0.5, 2.0);
+    ///
+    /// assert_eq!(Some(Vector3::new(1, 0, 2)), v.to_int::<i32>());
+    /// assert_eq!(Some(Vector3::new(1, -1, 2)), v.floor().to_int::<i64>());
+    /// ```
+    pub fn to_int<I: PrimInt>(self) -> Option<Vector3<I>> {
+        return self.cast();
+    }
+
+    /// Checks whether each component of this vector is within `epsilon` of the corresponding
+    /// component of the given vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(0.1 + 0.2, 1.0, 2.0);
+    /// let v2 = Vector3::new(0.3, 1.0, 2.0);
+    ///
+    /// assert_ne!(v1, v2);
+    /// assert!(v1.abs_diff_eq(v2, 1e-12));
+    /// ```
+    pub fn abs_diff_eq(self, other: Vector3<T>, epsilon: T) -> bool {
+        return (0..3).all(|i| (self[i] - other[i]).abs() <= epsilon);
+    }
+
+    /// Checks whether each component of this vector is approximately equal to the corresponding
+    /// component of the given vector, relative to the size of the components.
+    ///
+    /// Components are equal if they differ by at most `epsilon`, which handles values near zero,
+    /// or by at most `max_relative` times the larger of their magnitudes. Infinite components
+    /// are only equal to infinities of the same sign.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1e9 + 1.0, 0.0, -2.0);
+    /// let v2 = Vector3::new(1e9, 1e-20, -2.0);
+    ///
+    /// assert!(!v1.abs_diff_eq(v2, 1e-12));
+    /// assert!(v1.relative_eq(v2, 1e-12, 1e-6));
+    /// ```
+    pub fn relative_eq(self, other: Vector3<T>, epsilon: T, max_relative: T) -> bool {
+        return (0..3).all(|i| {
+            let (a, b) = (self[i], other[i]);
+            if a == b {
+                return true;
+            }
+            if a.is_infinite() || b.is_infinite() {
+                return false;
+            }
+            let difference = (a - b).abs();
+            difference <= epsilon || difference <= a.abs().max(b.abs()) * max_relative
+        });
+    }
+
+    /// Computes two vectors that form an orthonormal basis with this vector.
+    ///
+    /// This vector must already be normalized. The returned vectors `(v2, v3)` are chosen so that
+    /// `(self, v2, v3)` is a right-handed coordinate system.
+    ///
+    /// This uses the branchless construction from Duff et al., "Building an Orthonormal Basis,
+    /// Revisited", which stays accurate even as this vector approaches the -z axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(0.0, 0.0, 1.0);
+    /// let (v2, v3) = v1.coordinate_system();
+    ///
+    /// assert_eq!(Vector3::new(1.0, 0.0, 0.0), v2);
+    /// assert_eq!(Vector3::new(0.0, 1.0, 0.0), v3);
+    /// ```
+    pub fn coordinate_system(self) -> (Vector3<T>, Vector3<T>) {
+        let sign = T::one().copysign(self.z);
+        let a = -T::one() / (sign + self.z);
+        let b = self.x * self.y * a;
+        return (
+            Vector3::new(T::one() + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Vector3::new(b, sign + self.y * self.y * a, -self.y),
+        );
+    }
+
+    /// Creates the unit vector with the given spherical coordinates.
+    ///
+    /// `theta` is measured from the +z axis and `phi` is measured counterclockwise from the +x
+    /// axis in the xy plane. The sine and cosine of `theta` are clamped to `[-1, 1]` to absorb
+    /// rounding error from the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert_eq!(Vector3::new(0.0, 0.0, 1.0), Vector3::spherical_direction(0.0, 1.0, PI));
+    /// assert_eq!(Vector3::new(1.0, 0.0, 0.0), Vector3::spherical_direction(1.0, 0.0, 0.0));
+    /// ```
+    pub fn spherical_direction(sin_theta: T, cos_theta: T, phi: T) -> Self {
+        return Self::spherical_direction_trig(sin_theta, cos_theta, phi.sin(), phi.cos());
+    }
+
+    /// Creates the unit vector with the given spherical coordinates, given the sine and cosine
+    /// of both angles.
+    ///
+    /// This is `spherical_direction` for callers that already have the sine and cosine of `phi`,
+    /// such as sampling routines, and avoids computing them again. As there, the sine and
+    /// cosine of `theta` are clamped to `[-1, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let (sin_theta, cos_theta) = (0.6, 0.8);
+    /// let (sin_phi, cos_phi) = (1.0, 0.0);
+    /// let v = Vector3::spherical_direction_trig(sin_theta, cos_theta, sin_phi, cos_phi);
+    ///
+    /// assert_eq!(Vector3::new(0.0, 0.6, 0.8), v);
+    /// ```
+    pub fn spherical_direction_trig(sin_theta: T, cos_theta: T, sin_phi: T, cos_phi: T) -> Self {
+        let sin_theta = clamp(sin_theta, -T::one(), T::one());
+        let cos_theta = clamp(cos_theta, -T::one(), T::one());
+        return Self::new(sin_theta * cos_phi, sin_theta * sin_phi, cos_theta);
+    }
+
+    /// Creates the vector with the given spherical coordinates in the frame with the given axes.
+    ///
+    /// This is `spherical_direction` with `x`, `y`, and `z` in place of the coordinate axes. The
+    /// result is a unit vector if the axes form an orthonormal basis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let x = Vector3::new(0.0, 1.0, 0.0);
+    /// let y = Vector3::new(0.0, 0.0, 1.0);
+    /// let z = Vector3::new(1.0, 0.0, 0.0);
+    ///
+    /// assert_eq!(z, Vector3::spherical_direction_in_frame(0.0, 1.0, 0.0, x, y, z));
+    /// ```
+    pub fn spherical_direction_in_frame(
+        sin_theta: T,
+        cos_theta: T,
+        phi: T,
+        x: Vector3<T>,
+        y: Vector3<T>,
+        z: Vector3<T>,
+    ) -> Self {
+        let v = Self::spherical_direction(sin_theta, cos_theta, phi);
+        return x * v.x + y * v.y + z * v.z;
+    }
+
+    /// Computes the spherical angle `theta` of this vector, measured from the +z axis.
+    ///
+    /// This vector must already be normalized. The result is in `[0, π]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert_eq!(0.0, Vector3::new(0.0, 0.0, 1.0).spherical_theta());
+    /// assert_eq!(PI, Vector3::new(0.0, 0.0, -1.0).spherical_theta());
+    /// ```
+    pub fn spherical_theta(self) -> T {
+        return clamp(self.z, -T::one(), T::one()).acos();
+    }
+
+    /// Computes the spherical angle `phi` of this vector, measured counterclockwise from the +x
+    /// axis in the xy plane.
+    ///
+    /// The result is in `[0, 2π)`. Vectors along the z axis have no well-defined `phi`, so the
+    /// result for them is arbitrary, though it is still in range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert_eq!(PI / 2.0, Vector3::new(0.0, 1.0, 0.0).spherical_phi());
+    /// assert_eq!(3.0 * PI / 2.0, Vector3::new(0.0, -1.0, 0.0).spherical_phi());
+    /// ```
+    pub fn spherical_phi(self) -> T {
+        let two_pi = T::from(2.0 * core::f64::consts::PI).unwrap();
+        let phi = self.y.atan2(self.x);
+        let phi = if phi < T::zero() { phi + two_pi } else { phi };
+        // Adding 2π to a tiny negative angle rounds to exactly 2π, which is out of range.
+        return if phi >= two_pi { T::zero() } else { phi };
+    }
+
+    /// Creates the unit vector with the given spherical angles.
+    ///
+    /// This is `spherical_direction` with the angle `theta` instead of its sine and cosine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = Vector3::from_spherical(PI / 2.0, PI);
+    ///
+    /// assert!(v.abs_diff_eq(Vector3::new(-1.0, 0.0, 0.0), 1e-15));
+    /// ```
+    pub fn from_spherical(theta: T, phi: T) -> Self {
+        return Self::spherical_direction(theta.sin(), theta.cos(), phi);
+    }
+
+    /// Computes the spherical angles `(theta, phi)` of this vector.
+    ///
+    /// This vector must already be normalized. See `spherical_theta` and `spherical_phi` for the
+    /// ranges of the angles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert_eq!((PI / 2.0, 3.0 * PI / 2.0), Vector3::new(0.0, -1.0, 0.0).to_spherical());
+    /// ```
+    pub fn to_spherical(self) -> (T, T) {
+        return (self.spherical_theta(), self.spherical_phi());
+    }
+
+    /// Computes the projection of this vector onto the given vector.
+    ///
+    /// This is the component of this vector that is parallel to the other vector. Projecting onto
+    /// the zero vector yields a vector of NaNs, which panics in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(3.0, 4.0, 5.0);
+    ///
+    /// assert_eq!(Vector3::new(3.0, 0.0, 0.0), v.project_onto(Vector3::new(2.0, 0.0, 0.0)));
+    /// ```
+    pub fn project_onto(self, other: Vector3<T>) -> Vector3<T> {
+        return other * (self.dot(other) / other.dot(other));
+    }
+
+    /// Computes the rejection of this vector from the given vector.
+    ///
+    /// This is the component of this vector that is perpendicular to the other vector, so that
+    /// `v.project_onto(w) + v.reject_from(w)` is `v`. Rejecting from the zero vector yields a
+    /// vector of NaNs, which panics in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(3.0, 4.0, 5.0);
+    ///
+    /// assert_eq!(Vector3::new(0.0, 4.0, 5.0), v.reject_from(Vector3::new(2.0, 0.0, 0.0)));
+    /// ```
+    pub fn reject_from(self, other: Vector3<T>) -> Vector3<T> {
+        return self - self.project_onto(other);
+    }
+
+    /// Removes the component of this vector along the given normalized vector.
+    ///
+    /// This is one step of Gram-Schmidt orthogonalization, and is cheaper than `reject_from`
+    /// because `w` is assumed to already be normalized, which is checked in debug builds with
+    /// [`is_normalized`](Self::is_normalized). If this vector is parallel to `w` the result is the
+    /// zero vector (up to rounding), so callers building a frame should check for that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.0, 2.0, 3.0);
+    /// let w = Vector3::new(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(Vector3::new(1.0, 2.0, 0.0), v.gram_schmidt(w));
+    /// ```
+    pub fn gram_schmidt(self, w: Vector3<T>) -> Vector3<T> {
+        debug_assert!(w.is_normalized(), "Vector to orthogonalize against isn't normalized");
+        return self - w * self.dot(w);
+    }
+
+    /// Builds an orthonormal pair of vectors from this vector and another vector, using
+    /// Gram-Schmidt orthonormalization.
+    ///
+    /// The first vector of the pair is this vector normalized, and the second is the part of
+    /// `other` perpendicular to this vector, normalized. This is useful for building a tangent
+    /// frame from a normal and a reference direction. Returns `None` if either vector is zero or
+    /// if the vectors are parallel, including when they are so close to parallel that the second
+    /// vector's direction would be mostly rounding error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let a = Vector3::new(0.0, 0.0, 2.0);
+    /// let b = Vector3::new(1.0, 0.0, 1.0);
+    /// let expected = (Vector3::new(0.0, 0.0, 1.0), Vector3::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(Some(expected), a.gram_schmidt_pair(b));
+    /// assert_eq!(None, a.gram_schmidt_pair(a * -3.0));
+    /// ```
+    pub fn gram_schmidt_pair(self, other: Vector3<T>) -> Option<(Vector3<T>, Vector3<T>)> {
+        let length = self.length();
+        if length <= T::zero() {
+            return None;
+        }
+        let a = self / length;
+        // A second pass removes the error left by the first when the vectors are nearly
+        // parallel, so that the pair is orthogonal to within rounding.
+        let b = other.gram_schmidt(a).gram_schmidt(a);
+        let b_length = b.length();
+        if b_length <= other.length() * T::epsilon().sqrt() {
+            return None;
+        }
+        return Some((a, b / b_length));
+    }
+
+    /// Reflects this vector about the given normal.
+    ///
+    /// Following pbrt's convention, this vector is assumed to point away from the surface, and
+    /// the reflected vector also points away from the surface. The normal must be normalized.
+    /// For incoming directions that point towards the surface, use [`mirror`](Self::mirror).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let wo = Vector3::new(1.0, 1.0, 0.0);
+    /// let n = Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(Vector3::new(-1.0, 1.0, 0.0), wo.reflect(n));
+    /// ```
+    pub fn reflect(self, n: Vector3<T>) -> Vector3<T> {
+        let two = T::one() + T::one();
+        return n * (two * self.dot(n)) - self;
+    }
+
+    /// Reflects this vector across the plane perpendicular to the given normal.
+    ///
+    /// This computes `self - 2 * self.dot(n) * n`, which is the convention used when this vector
+    /// is an incoming direction pointing towards the surface. The result is the negation of
+    /// [`reflect`](Self::reflect). The normal must be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let d = Vector3::new(1.0, -1.0, 0.0);
+    /// let n = Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(Vector3::new(1.0, 1.0, 0.0), d.mirror(n));
+    /// ```
+    pub fn mirror(self, n: Vector3<T>) -> Vector3<T> {
+        let two = T::one() + T::one();
+        return self - n * (two * self.dot(n));
+    }
+
+    /// Refracts this vector through a surface with the given normal, using Snell's law.
+    ///
+    /// Following pbrt's convention, this vector is the normalized incident direction pointing
+    /// away from the surface, and the normal must be normalized and lie on the same side of the
+    /// surface as this vector. `eta` is the ratio of the index of refraction on the incident side
+    /// to the index of refraction on the transmitted side.
+    ///
+    /// Returns `None` if the light undergoes total internal reflection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let wi = Vector3::new(0.6, 0.0, 0.8);
+    /// let n = Vector3::new(0.0, 0.0, 1.0);
+    ///
+    /// // Light passes straight through when both sides have the same index of refraction.
+    /// let wt = wi.refract(n, 1.0).unwrap();
+    /// assert!(wt.abs_diff_eq(-wi, 1e-12));
+    ///
+    /// // Light leaving glass at a grazing angle is totally internally reflected.
+    /// let wi = Vector3::new(0.8, 0.0, 0.6);
+    /// assert_eq!(None, wi.refract(n, 1.5));
+    /// ```
+    pub fn refract(self, n: Vector3<T>, eta: T) -> Option<Vector3<T>> {
+        let cos_theta_i = n.dot(self);
+        let sin2_theta_i = (T::one() - cos_theta_i * cos_theta_i).max(T::zero());
+        let sin2_theta_t = eta * eta * sin2_theta_i;
+        if sin2_theta_t >= T::one() {
+            return None;
+        }
+        let cos_theta_t = (T::one() - sin2_theta_t).sqrt();
+        return Some(-self * eta + n * (eta * cos_theta_i - cos_theta_t));
+    }
+
+    /// Computes `self * a + b` component-wise, with each component rounded only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.0, 2.0, 3.0);
+    /// let a = Vector3::new(2.0, 0.5, -1.0);
+    /// let b = Vector3::new(0.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(Vector3::new(2.0, 2.0, -2.0), v.mul_add(a, b));
+    /// ```
+    pub fn mul_add(self, a: Vector3<T>, b: Vector3<T>) -> Self {
+        return Self::new(
+            self.x.mul_add(a.x, b.x),
+            self.y.mul_add(a.y, b.y),
+            self.z.mul_add(a.z, b.z));
+    }
+
+    /// Computes the dot product of this vector with the given vector, accumulating the terms with
+    /// fused multiply-adds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1.0, 2.0, 3.0);
+    /// let v2 = Vector3::new(4.0, -5.0, 6.0);
+    ///
+    /// assert_eq!(v1.dot(v2), v1.dot_fma(v2));
+    /// ```
+    pub fn dot_fma(self, other: Vector3<T>) -> T {
+        let dot = self.x.mul_add(other.x, self.y.mul_add(other.y, self.z * other.z));
+        debug_assert!(!dot.is_nan(), "Dot product is NaN");
+        return dot;
+    }
+}
+
+impl<T: Float + DifferenceOfProducts> Vector3<T> {
+    /// Computes the angle between this vector and the given vector, in radians.
+    ///
+    /// The result is in `[0, π]`. Neither vector needs to be normalized. This is computed as
+    /// `atan2(|a × b|, a · b)`, which stays accurate for nearly parallel and nearly opposite
+    /// vectors, unlike `acos` of the normalized dot product.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let v1 = Vector3::new(2.0, 0.0, 0.0);
+    /// let v2 = Vector3::new(0.0, 0.0, 3.0);
+    ///
+    /// assert_eq!(FRAC_PI_2, v1.angle_between(v2));
+    /// ```
+    pub fn angle_between(self, other: Vector3<T>) -> T {
+        let cross = self.cross(other);
+        return cross.dot(cross).sqrt().atan2(self.dot(other));
+    }
+
+    /// Spherically interpolates between this unit vector and another, with `t` in `[0, 1]`.
+    ///
+    /// Unlike normalizing the result of `lerp`, the result turns at a constant angular speed as
+    /// `t` changes. A `t` of 0 gives exactly this vector and a `t` of 1 gives exactly the other
+    /// vector. Nearly parallel vectors, where the spherical formula would divide by almost
+    /// zero, fall back to a normalized `lerp`.
+    ///
+    /// Opposite vectors are joined by infinitely many shortest arcs, so interpolating between
+    /// them isn't meaningful and the result is unspecified; pick an intermediate direction and
+    /// interpolate in two steps instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v1 = Vector3::new(1.0, 0.0, 0.0);
+    /// let v2 = Vector3::new(0.0, 1.0, 0.0);
+    /// let expected = Vector3::new(3.0f64.sqrt() / 2.0, 0.5, 0.0);
+    ///
+    /// assert!(v1.slerp(v2, 1.0 / 3.0).abs_diff_eq(expected, 1e-15));
+    /// ```
+    pub fn slerp(self, other: Vector3<T>, t: T) -> Vector3<T> {
+        let theta = self.angle_between(other);
+        if theta < T::epsilon().sqrt() {
+            let v = self.lerp(other, t);
+            return v / v.length();
+        }
+        let sin_theta = theta.sin();
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        return self * a + other * b;
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T: Float> Vector3<T>
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    /// Generates a direction uniformly distributed over the unit sphere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v: Vector3<f64> = Vector3::random_unit(&mut rand::thread_rng());
+    ///
+    /// assert!((v.length() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn random_unit<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let two = T::one() + T::one();
+        let z = T::one() - two * rng.gen::<T>();
+        let r = (T::one() - z * z).max(T::zero()).sqrt();
+        let phi = two * T::from(core::f64::consts::PI).unwrap() * rng.gen::<T>();
+        return Self::new(r * phi.cos(), r * phi.sin(), z);
+    }
+
+    /// Generates a point uniformly distributed inside the unit ball.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v: Vector3<f64> = Vector3::random_in_unit_sphere(&mut rand::thread_rng());
+    ///
+    /// assert!(v.length() <= 1.0);
+    /// ```
+    pub fn random_in_unit_sphere<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        return Self::random_unit(rng) * rng.gen::<T>().cbrt();
+    }
+
+    /// Generates a direction uniformly distributed over the hemisphere around the given normal.
+    ///
+    /// The normal doesn't need to be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let n = Vector3::new(0.0, 0.0, 2.0);
+    /// let v = Vector3::random_on_hemisphere(&mut rand::thread_rng(), n);
+    ///
+    /// assert!(v.dot(n) >= 0.0);
+    /// ```
+    pub fn random_on_hemisphere<R: rand::Rng + ?Sized>(rng: &mut R, normal: Vector3<T>) -> Self {
+        let v = Self::random_unit(rng);
+        return if v.dot(normal) < T::zero() { -v } else { v };
+    }
+}
+
+impl<T: Num> Add for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn add(self, other: Vector3<T>) -> Vector3<T> {
+        return Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z);
+    }
+}
+
+impl<T: Num + Copy> AddAssign for Vector3<T> {
+    fn add_assign(&mut self, other: Vector3<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Num> Sub for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, other: Vector3<T>) -> Vector3<T> {
+        return Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z);
+    }
+}
+
+impl<T: Num + Copy> SubAssign for Vector3<T> {
+    fn sub_assign(&mut self, other: Vector3<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Num + Copy> Mul<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, scalar: T) -> Vector3<T> {
+        return Vector3::new(self.x * scalar, self.y * scalar, self.z * scalar);
+    }
+}
+
+impl<T: Num + Copy> MulAssign<T> for Vector3<T> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
+    }
+}
+
+impl<T: Num + Copy> Div<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn div(self, scalar: T) -> Vector3<T> {
+        return Vector3::new(self.x / scalar, self.y / scalar, self.z / scalar);
+    }
+}
+
+impl<T: Num + Copy> DivAssign<T> for Vector3<T> {
+    fn div_assign(&mut self, scalar: T) {
+        *self = *self / scalar;
+    }
+}
+
+impl<T: Num + fmt::Display> fmt::Display for Vector3<T> {
+    /// Formats this vector as `[x, y, z]`.
+    ///
+    /// Brackets rather than parentheses keep vectors distinct from debug-printed tuples in logs.
+    ///
+    /// Formatting parameters such as width, precision, and sign are applied to each component.
+    /// Integer components ignore precision, as they do when formatted on their own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    ///
+    /// let v = Vector3::new(1.0, -2.5, 1.0 / 3.0);
+    ///
+    /// assert_eq!("[1.00, -2.50, 0.33]", format!("{:.2}", v));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.z, f)?;
+        return write!(f, "]");
+    }
+}
+
+impl<T: Num + Ord> PartialOrd for Vector3<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<T: Num + Ord> Ord for Vector3<T> {
+    /// Compares vectors lexicographically by `x`, then `y`, then `z`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.x.cmp(&other.x)
+            .then_with(|| self.y.cmp(&other.y))
+            .then_with(|| self.z.cmp(&other.z));
+    }
+}
+
+impl<T: Num> Default for Vector3<T> {
+    /// Creates the zero vector.
+    fn default() -> Self {
+        Vector3::zero()
+    }
+}
+
+impl<T: Num> From<[T; 3]> for Vector3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Vector3::new(x, y, z)
+    }
+}
+
+impl<T: Num> From<Vector3<T>> for [T; 3] {
+    fn from(v: Vector3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T: Num> From<(T, T, T)> for Vector3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vector3::new(x, y, z)
+    }
+}
+
+impl<T: Num> From<Vector3<T>> for (T, T, T) {
+    fn from(v: Vector3<T>) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl<T: Num> IntoIterator for Vector3<T> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, 3>;
+
+    /// Returns an iterator over the components of this vector, in x, y, z order.
+    fn into_iter(self) -> Self::IntoIter {
+        return IntoIterator::into_iter([self.x, self.y, self.z]);
+    }
+}
+
+impl<'a, T: Num> IntoIterator for &'a Vector3<T> {
+    type Item = &'a T;
+    type IntoIter = core::array::IntoIter<&'a T, 3>;
+
+    /// Returns an iterator over references to the components of this vector, in x, y, z order.
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<T: Num> FromIterator<T> for Vector3<T> {
+    /// Creates a vector from an iterator of exactly three components, in x, y, z order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator doesn't yield exactly three components.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut next = |count| match iter.next() {
+            Some(c) => c,
+            None => panic!("Expected 3 components for Vector3, but got {}", count),
+        };
+        let (x, y, z) = (next(0), next(1), next(2));
+        if iter.next().is_some() {
+            panic!("Expected 3 components for Vector3, but got more");
+        }
+        return Vector3::new(x, y, z);
+    }
+}
+
+impl<T: Num + Copy> Sum for Vector3<T> {
+    /// Adds up vectors, starting from the zero vector.
+    fn sum<I: Iterator<Item = Vector3<T>>>(iter: I) -> Self {
+        return iter.fold(Vector3::zero(), |sum, v| sum + v);
+    }
+}
+
+impl<'a, T: Num + Copy> Sum<&'a Vector3<T>> for Vector3<T> {
+    /// Adds up vectors, starting from the zero vector.
+    fn sum<I: Iterator<Item = &'a Vector3<T>>>(iter: I) -> Self {
+        return iter.copied().sum();
+    }
+}
+
+impl<T: Num + Copy> TryFrom<&[T]> for Vector3<T> {
+    type Error = TryFromSliceError;
+
+    /// Creates a vector from a slice of exactly three components.
+    fn try_from(slice: &[T]) -> Result<Self, TryFromSliceError> {
+        return match *slice {
+            [x, y, z] => Ok(Vector3::new(x, y, z)),
+            _ => Err(TryFromSliceError { length: slice.len() }),
+        };
+    }
+}
+
+/// The error returned when converting a slice that doesn't have exactly three elements into a
+/// vector.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TryFromSliceError {
+    /// The length of the slice.
+    pub length: usize,
+}
+
+impl fmt::Display for TryFromSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "expected a slice of length 3, but got length {}", self.length);
+    }
+}
+
+impl Error for TryFromSliceError {}
+
+/// Serializes this vector as the sequence `[x, y, z]`.
+#[cfg(feature = "serde")]
+impl<T: Num + serde::Serialize> serde::Serialize for Vector3<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return (&self.x, &self.y, &self.z).serialize(serializer);
+    }
+}
+
+/// Deserializes a vector from the sequence `[x, y, z]`.
+///
+/// NaN components are rejected with an error, since they'd otherwise break the invariant that
+/// [`Vector3::new`] checks.
+#[cfg(feature = "serde")]
+impl<'de, T: Num + serde::Deserialize<'de>> serde::Deserialize<'de> for Vector3<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y, z) = serde::Deserialize::deserialize(deserializer)?;
+        if is_nan(&x) || is_nan(&y) || is_nan(&z) {
+            return Err(serde::de::Error::custom("Vector3 has NaN components"));
+        }
+        return Ok(Vector3 { x, y, z });
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Num + approx::AbsDiffEq> approx::AbsDiffEq for Vector3<T>
+where
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        return T::default_epsilon();
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        return self.x.abs_diff_eq(&other.x, epsilon)
+            && self.y.abs_diff_eq(&other.y, epsilon)
+            && self.z.abs_diff_eq(&other.z, epsilon);
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Num + approx::RelativeEq> approx::RelativeEq for Vector3<T>
+where
+    T::Epsilon: Copy,
+{
+    fn default_max_relative() -> T::Epsilon {
+        return T::default_max_relative();
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        return self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative)
+            && self.z.relative_eq(&other.z, epsilon, max_relative);
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Num + approx::UlpsEq> approx::UlpsEq for Vector3<T>
+where
+    T::Epsilon: Copy,
+{
+    fn default_max_ulps() -> u32 {
+        return T::default_max_ulps();
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        return self.x.ulps_eq(&other.x, epsilon, max_ulps)
+            && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+            && self.z.ulps_eq(&other.z, epsilon, max_ulps);
+    }
+}
+
+/// Implements `bytemuck::Zeroable` and `bytemuck::Pod` for vectors of primitive numbers.
+///
+/// These are safe because `Vector3` is `#[repr(C)]` and made up of three fields of the same
+/// `Pod` type, so it has no padding. The size of each vector is checked at compile time.
+#[cfg(feature = "bytemuck")]
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(
+            const _: () = assert!(
+                core::mem::size_of::<Vector3<$t>>() == 3 * core::mem::size_of::<$t>());
+            unsafe impl bytemuck::Zeroable for Vector3<$t> {}
+            unsafe impl bytemuck::Pod for Vector3<$t> {}
+        )*
+    };
+}
+
+#[cfg(feature = "bytemuck")]
+impl_pod!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+#[cfg(feature = "glam")]
+impl From<Vector3<f32>> for glam::Vec3 {
+    fn from(v: Vector3<f32>) -> Self {
+        glam::Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec3> for Vector3<f32> {
+    fn from(v: glam::Vec3) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Vector3<f64>> for glam::DVec3 {
+    fn from(v: Vector3<f64>) -> Self {
+        glam::DVec3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::DVec3> for Vector3<f64> {
+    fn from(v: glam::DVec3) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<Vector3<f32>> for nalgebra::Vector3<f32> {
+    fn from(v: Vector3<f32>) -> Self {
+        nalgebra::Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Vector3<f32>> for Vector3<f32> {
+    fn from(v: nalgebra::Vector3<f32>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<Vector3<f64>> for nalgebra::Vector3<f64> {
+    fn from(v: Vector3<f64>) -> Self {
+        nalgebra::Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::Vector3<f64>> for Vector3<f64> {
+    fn from(v: nalgebra::Vector3<f64>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Num> Index<usize> for Vector3<T> {
+    type Output = T;
+
+    /// Gets a component by index, where 0, 1, and 2 correspond to x, y, and z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 2.
+    fn index(&self, index: usize) -> &T {
+        return match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds for Vector3: {}", index),
+        };
+    }
+}
+
+impl<T: Num> IndexMut<usize> for Vector3<T> {
+    /// Mutably gets a component by index, where 0, 1, and 2 correspond to x, y, and z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than 2.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        return match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds for Vector3: {}", index),
+        };
+    }
+}
+
+/// Implements scalar-times-vector multiplication for primitive scalar types.
+///
+/// The derived `Mul` only covers `vector * scalar`, and the orphan rule prevents a generic
+/// `impl<T> Mul<Vector3<T>> for T`, so each scalar type needs its own impl.
+macro_rules! impl_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<Vector3<$t>> for $t {
+                type Output = Vector3<$t>;
+
+                fn mul(self, v: Vector3<$t>) -> Vector3<$t> {
+                    return v * self;
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Checks whether a value is NaN, which is the only value that isn't equal to itself.
+#[allow(clippy::eq_op)]
+fn is_nan<T: PartialEq>(value: &T) -> bool {
+    return value != value;
+}
+
+/// Clamps a value between two bounds, preferring the lower bound if the bounds are crossed.
+fn clamp<T: PartialOrd>(value: T, lo: T, hi: T) -> T {
+    let value = if value > hi { hi } else { value };
+    return if value < lo { lo } else { value };
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_vectors_are_equal() {
+        let v1 = Vector3::new(1, 2, 3);
+        let v2 = Vector3::new(1, 2, 3);
+
+        assert_eq!(v1, v2);
+    }
+
+    #[test]
+    fn different_vectors_are_not_equal() {
+        let v1 = Vector3::new(1, 2, 3);
+        let v2 = Vector3::new(-3, 0, 5);
+
+        assert_ne!(v1, v2);
+    }
+
+    mod constants {
+        use super::*;
+
+        const UP: Vector3<f64> = Vector3::new_const(0.0, 1.0, 0.0);
+        const FORWARD: Vector3<f64> = Vector3::new_const(0.0, 0.0, -1.0);
+        const HALF: Vector3<f32> = Vector3::new_const(0.5, 0.5, 0.5);
+        const ORIGIN: Vector3<i32> = Vector3::new_const(0, 0, 0);
+        const STEP: Vector3<i64> = Vector3::new_const(1, -1, 2);
+
+        #[test]
+        fn new_const() {
+            assert_eq!(Vector3::new(0.0, 1.0, -1.0), UP + FORWARD);
+            assert_eq!(Vector3::new(-1.0, 0.0, 0.0), UP.cross(FORWARD));
+            assert_eq!(Vector3::new(1.0f32, 1.0, 1.0), HALF * 2.0);
+            assert_eq!(ORIGIN, Vector3::zero());
+            assert_eq!(Vector3::new(3, -3, 6), STEP * 3);
+        }
+    }
+
+    #[test]
+    fn zero() {
+        let v = Vector3::new(1.5, -2.0, 3.0);
+
+        assert_eq!(Vector3::new(0, 0, 0), Vector3::zero());
+        assert_eq!(v, v + Vector3::zero());
+        assert_eq!(Vector3::zero(), v * 0.0);
+    }
+
+    #[test]
+    fn one() {
+        assert_eq!(Vector3::new(1, 1, 1), Vector3::one());
+        assert_eq!(Vector3::new(1.0, 1.0, 1.0), Vector3::one());
+        let sum = Vector3::unit_x() + Vector3::unit_y() + Vector3::unit_z();
+        assert_eq!(Vector3::<i32>::one(), sum);
+    }
+
+    #[test]
+    fn splat() {
+        assert_eq!(Vector3::new(-1.5, -1.5, -1.5), Vector3::splat(-1.5));
+    }
+
+    #[test]
+    fn unit_axes() {
+        assert_eq!(Vector3::new(1, 0, 0), Vector3::unit_x());
+        assert_eq!(Vector3::new(0, 1, 0), Vector3::unit_y());
+        assert_eq!(Vector3::new(0, 0, 1), Vector3::unit_z());
+        assert_eq!(Vector3::<i32>::unit_z(), Vector3::unit_x().cross(Vector3::unit_y()));
+        assert_eq!(Vector3::<f64>::unit_x(), Vector3::unit_y().cross(Vector3::unit_z()));
+        assert_eq!(Vector3::<f64>::unit_y(), Vector3::unit_z().cross(Vector3::unit_x()));
+    }
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(Vector3::new(0.0, 0.0, 0.0), Vector3::<f64>::default());
+        assert_eq!(Vector3::new(0, 0, 0), Vector3::<i32>::default());
+        assert_eq!(vec![Vector3::new(0u8, 0, 0); 2], vec![Vector3::default(); 2]);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("[1, -2, 3]", Vector3::new(1, -2, 3).to_string());
+        assert_eq!("[1.5, -2, 0.25]", Vector3::new(1.5, -2.0, 0.25).to_string());
+    }
+
+    #[test]
+    fn display_precision() {
+        let v = Vector3::new(1.0, -2.0, 2.0 / 3.0);
+
+        assert_eq!("[1.000, -2.000, 0.667]", format!("{:.3}", v));
+        assert_eq!("[1, -2, 1]", format!("{:.0}", v));
+        assert_eq!("[+1.0, -2.0, +0.7]", format!("{:+.1}", v));
+        assert_eq!("[1.00, -2.00, 0.67]", format!("{:.*}", 2, v));
+    }
+
+    #[test]
+    fn display_precision_is_ignored_for_integers() {
+        assert_eq!("[1, -2, 3]", format!("{:.2}", Vector3::new(1, -2, 3)));
+    }
+
+    #[test]
+    fn display_width() {
+        assert_eq!("[       1,       -2,        3]", format!("{:>8}", Vector3::new(1, -2, 3)));
+        assert_eq!("[1.50  , -2.00 , 0.25  ]", format!("{:<6.2}", Vector3::new(1.5, -2.0, 0.25)));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use super::*;
+
+        #[test]
+        fn serialize() {
+            let v = Vector3::new(1.5, -2.0, 0.25);
+
+            assert_eq!("[1.5,-2.0,0.25]", serde_json::to_string(&v).unwrap());
+        }
+
+        #[test]
+        fn deserialize() {
+            let v: Vector3<i32> = serde_json::from_str("[1, -2, 3]").unwrap();
+
+            assert_eq!(Vector3::new(1, -2, 3), v);
+        }
+
+        #[test]
+        fn deserialize_wrong_length() {
+            assert!(serde_json::from_str::<Vector3<i32>>("[1, -2]").is_err());
+            assert!(serde_json::from_str::<Vector3<i32>>("[1, -2, 3, 4]").is_err());
+        }
+
+        #[test]
+        fn deserialize_nan() {
+            use ::serde::de::value::{Error, SeqDeserializer};
+            use ::serde::Deserialize;
+
+            let components = vec![1.0, f64::NAN, 0.0].into_iter();
+            let deserializer = SeqDeserializer::<_, Error>::new(components);
+            let error = Vector3::<f64>::deserialize(deserializer).unwrap_err();
+
+            assert_eq!("Vector3 has NaN components", error.to_string());
+        }
+
+        #[test]
+        fn round_trip() {
+            let v = Vector3::new(0.1, -2.0, 1e-30);
+            let json = serde_json::to_string(&v).unwrap();
+
+            assert_eq!(v, serde_json::from_str(&json).unwrap());
+
+            let v = Vector3::new(i64::MIN, 0, i64::MAX);
+            let json = serde_json::to_string(&v).unwrap();
+
+            assert_eq!(v, serde_json::from_str(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(3 * std::mem::size_of::<f32>(), std::mem::size_of::<Vector3<f32>>());
+        assert_eq!(3 * std::mem::size_of::<u8>(), std::mem::size_of::<Vector3<u8>>());
+        assert_eq!(std::mem::align_of::<f64>(), std::mem::align_of::<Vector3<f64>>());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    mod bytemuck {
+        use super::*;
+
+        #[test]
+        fn cast_slice() {
+            let vectors = vec![Vector3::new(1.0f32, 2.0, 3.0), Vector3::new(-4.0, 5.5, 0.0)];
+            let floats: &[f32] = ::bytemuck::cast_slice(&vectors);
+
+            assert_eq!(&[1.0, 2.0, 3.0, -4.0, 5.5, 0.0], floats);
+
+            let bytes: &[u8] = ::bytemuck::cast_slice(&vectors);
+
+            assert_eq!(24, bytes.len());
+            assert_eq!(&2.0f32.to_ne_bytes(), &bytes[4..8]);
+        }
+
+        #[test]
+        fn zeroed() {
+            let v: Vector3<i64> = ::bytemuck::Zeroable::zeroed();
+
+            assert_eq!(Vector3::new(0, 0, 0), v);
+        }
+
+        #[test]
+        fn cast_slice_round_trip() {
+            let floats = [0.5f32, -1.0, 2.0, 3.0, 4.0, -5.25];
+            let vectors: &[Vector3<f32>] = ::bytemuck::cast_slice(&floats);
+
+            assert_eq!(&[Vector3::new(0.5, -1.0, 2.0), Vector3::new(3.0, 4.0, -5.25)], vectors);
+            assert_eq!(&floats, ::bytemuck::cast_slice::<Vector3<f32>, f32>(vectors));
+            assert!(::bytemuck::try_cast_slice::<f32, Vector3<f32>>(&floats[..4]).is_err());
+        }
+
+        #[test]
+        fn only_primitives_are_pod() {
+            // Inherent associated items take priority over trait ones, but only apply when the
+            // impl's bounds hold, so this is `true` exactly when `T: Pod`.
+            trait NotPod {
+                const IS_POD: bool = false;
+            }
+            impl<T> NotPod for T {}
+            struct IsPod<T>(std::marker::PhantomData<T>);
+            impl<T: ::bytemuck::Pod> IsPod<T> {
+                const IS_POD: bool = true;
+            }
+
+            const _: () = assert!(IsPod::<Vector3<f32>>::IS_POD);
+            const _: () = assert!(IsPod::<Vector3<u8>>::IS_POD);
+            const _: () = assert!(!IsPod::<Vector3<num::Complex<f32>>>::IS_POD);
+            const _: () = assert!(!IsPod::<Vector3<num::rational::Ratio<i32>>>::IS_POD);
+        }
+    }
+
+    mod conversions {
+        use super::*;
+
+        #[test]
+        fn array() {
+            let v = Vector3::from([1, -2, 3]);
+
+            assert_eq!(Vector3::new(1, -2, 3), v);
+
+            let [x, y, z]: [i32; 3] = v.into();
+
+            assert_eq!((1, -2, 3), (x, y, z));
+            assert_eq!([1, -2, 3], v.as_array());
+        }
+
+        #[test]
+        fn array_round_trip() {
+            let array = [0.5, -1.25, 3.0];
+            let v: Vector3<f64> = array.into();
+
+            assert_eq!(array, <[f64; 3]>::from(v));
+
+            let v = Vector3::new(7u16, 0, 65535);
+            let array: [u16; 3] = v.into();
+
+            assert_eq!(v, Vector3::from(array));
+        }
+
+        #[test]
+        fn tuple() {
+            let v = Vector3::from((1, -2, 3));
+
+            assert_eq!(Vector3::new(1, -2, 3), v);
+            assert_eq!((1, -2, 3), v.into());
+        }
+
+        #[test]
+        fn slice() {
+            let components = [1.0, 2.0, 3.0];
+
+            assert_eq!(Ok(Vector3::new(1.0, 2.0, 3.0)), Vector3::try_from(&components[..]));
+        }
+
+        #[test]
+        fn slice_wrong_length() {
+            let components = [1, 2, 3, 4];
+
+            assert_eq!(Err(TryFromSliceError { length: 2 }), Vector3::try_from(&components[..2]));
+            assert_eq!(Err(TryFromSliceError { length: 4 }), Vector3::try_from(&components[..]));
+            assert_eq!(
+                "expected a slice of length 3, but got length 0",
+                Vector3::try_from(&components[..0]).unwrap_err().to_string());
+        }
+
+        #[test]
+        fn into_iter() {
+            let v = Vector3::new(1, -2, 3);
+
+            assert_eq!(vec![1, -2, 3], v.into_iter().collect::<Vec<_>>());
+            assert_eq!(2, v.into_iter().sum::<i32>());
+
+            let mut components = Vec::new();
+            for c in v {
+                components.push(c);
+            }
+
+            assert_eq!(vec![1, -2, 3], components);
+        }
+
+        #[test]
+        fn into_iter_ref() {
+            let v = Vector3::new(1.5, -2.0, 3.0);
+            let mut components = Vec::new();
+            for c in &v {
+                components.push(c);
+            }
+
+            assert_eq!(vec![&1.5, &-2.0, &3.0], components);
+        }
+
+        #[test]
+        fn from_iter() {
+            let v: Vector3<i32> = (1..=3).collect();
+
+            assert_eq!(Vector3::new(1, 2, 3), v);
+            assert_eq!(v, v.into_iter().collect());
+            assert_eq!(v * 2, v.iter().map(|c| c * 2).collect());
+        }
+
+        #[test]
+        #[should_panic(expected = "Expected 3 components for Vector3, but got 2")]
+        fn from_iter_too_short() {
+            let _: Vector3<i32> = vec![1, 2].into_iter().collect();
+        }
+
+        #[test]
+        #[should_panic(expected = "Expected 3 components for Vector3, but got more")]
+        fn from_iter_too_long() {
+            let _: Vector3<i32> = (1..=4).collect();
+        }
+
+        #[test]
+        fn sum() {
+            let directions = [
+                Vector3::new(1.0, 0.0, 0.5),
+                Vector3::new(0.0, 2.0, -0.5),
+                Vector3::new(-3.0, 1.0, 1.0),
+            ];
+            let total: Vector3<f64> = directions.iter().copied().sum();
+
+            assert_eq!(Vector3::new(-2.0, 3.0, 1.0), total);
+        }
+
+        #[test]
+        fn sum_refs() {
+            let v1 = Vector3::new(1, 0, 5);
+            let v2 = Vector3::new(0, 2, -5);
+            let v3 = Vector3::new(-3, 1, 1);
+
+            assert_eq!(v1 + v2 + v3, [v1, v2, v3].iter().sum());
+        }
+
+        #[test]
+        fn sum_empty() {
+            let total: Vector3<i32> = Vec::<Vector3<i32>>::new().into_iter().sum();
+
+            assert_eq!(Vector3::zero(), total);
+        }
+
+        #[test]
+        fn iter() {
+            let v = Vector3::new(1, -2, 3);
+            let components: Vec<&i32> = v.iter().collect();
+
+            assert_eq!(vec![&1, &-2, &3], components);
+        }
+    }
+
+    #[cfg(feature = "approx")]
+    mod approx {
+        use super::*;
+        use ::approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+        #[test]
+        fn abs_diff_eq() {
+            let v = Vector3::new(0.1, 0.2, 0.3);
+
+            assert_abs_diff_eq!(v, Vector3::new(0.1 + 1e-10, 0.2, 0.3 - 1e-10), epsilon = 1e-9);
+            assert!(::approx::AbsDiffEq::abs_diff_ne(&v, &Vector3::new(0.1, 0.2, 0.31), 1e-9));
+        }
+
+        #[test]
+        fn relative_eq() {
+            let v = Vector3::new(1e10, -1e-10, 1.0);
+
+            assert_relative_eq!(v, v * (1.0 + 1e-12), max_relative = 1e-9);
+            assert_relative_ne!(v, Vector3::new(1e10, -2e-10, 1.0), max_relative = 1e-9);
+        }
+
+        #[test]
+        fn ulps_eq() {
+            let v = Vector3::new(0.1f32, 0.2, 0.3);
+
+            assert_ulps_eq!(v, Vector3::new(0.1, 0.2, 0.1 + 0.2));
+        }
+
+        #[test]
+        fn length_squared() {
+            let v = Vector3::new(0.1, -0.2, 0.3);
+
+            assert_relative_eq!(0.14, v.length_squared(), epsilon = 1e-15);
+        }
+
+        #[test]
+        fn length() {
+            let v = Vector3::new(0.1, -0.2, 0.3);
+
+            assert_relative_eq!(0.14f64.sqrt(), v.length(), epsilon = 1e-15);
+            assert_relative_eq!(1.0, v.normalize().length(), epsilon = 1e-15);
+        }
+    }
+
+    #[cfg(feature = "glam")]
+    mod glam {
+        use super::*;
+
+        #[test]
+        fn vec3() {
+            let v = Vector3::new(1.0f32, -2.0, 3.5);
+            let g: ::glam::Vec3 = v.into();
+
+            assert_eq!(::glam::Vec3::new(1.0, -2.0, 3.5), g);
+            assert_eq!(v, Vector3::from(g));
+        }
+
+        #[test]
+        fn dvec3() {
+            let v = Vector3::new(1.0, -2.0, 3.5);
+            let g: ::glam::DVec3 = v.into();
+
+            assert_eq!(::glam::DVec3::new(1.0, -2.0, 3.5), g);
+            assert_eq!(v, Vector3::from(g));
+        }
+
+        #[test]
+        fn round_trip_preserves_bits() {
+            let v = Vector3::new(-0.0f32, f32::MIN_POSITIVE / 4.0, -f32::MAX);
+            let round_trip = Vector3::from(::glam::Vec3::from(v));
+
+            assert_eq!(v.map(f32::to_bits), round_trip.map(f32::to_bits));
+
+            let v = Vector3::new(-0.0f64, f64::MIN_POSITIVE / 4.0, -f64::MAX);
+            let round_trip = Vector3::from(::glam::DVec3::from(v));
+
+            assert_eq!(v.map(f64::to_bits), round_trip.map(f64::to_bits));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    mod rand {
+        use super::*;
+        use ::rand::rngs::StdRng;
+        use ::rand::SeedableRng;
+
+        const SAMPLES: usize = 100_000;
+
+        fn mean(samples: impl Iterator<Item = Vector3<f64>>) -> Vector3<f64> {
+            return samples.sum::<Vector3<f64>>() / SAMPLES as f64;
+        }
+
+        #[test]
+        fn random_unit() {
+            let mut rng = StdRng::seed_from_u64(1);
+            let samples: Vec<Vector3<f64>> =
+                (0..SAMPLES).map(|_| Vector3::random_unit(&mut rng)).collect();
+
+            assert!(samples.iter().all(|v| (v.length() - 1.0).abs() < 1e-12));
+            assert!(mean(samples.into_iter()).length() < 0.01);
+        }
+
+        #[test]
+        fn random_in_unit_sphere() {
+            let mut rng = StdRng::seed_from_u64(2);
+            let samples: Vec<Vector3<f64>> =
+                (0..SAMPLES).map(|_| Vector3::random_in_unit_sphere(&mut rng)).collect();
+
+            assert!(samples.iter().all(|v| v.length() <= 1.0));
+            assert!(mean(samples.iter().copied()).length() < 0.01);
+
+            // Half of the volume of the unit ball lies outside the radius 0.5^(1/3).
+            let outer = samples.iter().filter(|v| v.length() > 0.5f64.cbrt()).count();
+            assert!((outer as f64 / SAMPLES as f64 - 0.5).abs() < 0.01);
+        }
+
+        #[test]
+        fn random_on_hemisphere() {
+            let mut rng = StdRng::seed_from_u64(3);
+            let n = Vector3::new(1.0, -2.0, 0.5);
+            let samples: Vec<Vector3<f64>> =
+                (0..SAMPLES).map(|_| Vector3::random_on_hemisphere(&mut rng, n)).collect();
+
+            assert!(samples.iter().all(|v| v.dot(n) >= 0.0));
+
+            // The mean direction of a uniform hemisphere is half the unit normal.
+            let expected = n / n.length() * 0.5;
+            assert!(mean(samples.into_iter()).abs_diff_eq(expected, 0.01));
+        }
+    }
+
+    mod slices {
+        use super::*;
+        use ::rand::rngs::StdRng;
+        use ::rand::{Rng, SeedableRng};
+
+        /// Generates vectors from a seeded random number generator, so failures reproduce.
+        fn random_vectors(count: usize, seed: u64) -> Vec<Vector3<f32>> {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut next = move || rng.gen_range(-100.0..100.0);
+            return (0..count).map(|_| Vector3::new(next(), next(), next())).collect();
+        }
+
+        #[test]
+        fn total() {
+            for &count in &[0, 1, 1000] {
+                let vectors = random_vectors(count, 1);
+                let mut expected = Vector3::zero();
+                for v in &vectors {
+                    expected += *v;
+                }
+
+                assert_eq!(expected, Vector3::total(&vectors));
+            }
+        }
+
+        #[test]
+        fn centroid() {
+            assert_eq!(None, Vector3::<f32>::centroid(&[]));
+
+            for &count in &[1, 1000] {
+                let points = random_vectors(count, 2);
+                let mut sum = [0.0f64; 3];
+                for p in &points {
+                    for i in 0..3 {
+                        sum[i] += p[i] as f64;
+                    }
+                }
+                let n = count as f64;
+                let expected = Vector3::new(sum[0] / n, sum[1] / n, sum[2] / n);
+
+                assert_eq!(Some(expected), Vector3::centroid(&points));
+            }
+        }
+
+        #[test]
+        fn centroid_accumulates_in_f64() {
+            // Each 1.0 would be lost to rounding if it were added to 1e8 in f32.
+            let mut points = vec![Vector3::new(1e8f32, 0.0, 0.0)];
+            points.extend(vec![Vector3::new(1.0, 0.0, 0.0); 1000]);
+
+            let expected = Vector3::new((1e8 + 1000.0) / 1001.0, 0.0, 0.0);
+            assert_eq!(Some(expected), Vector3::centroid(&points));
+        }
+
+        #[test]
+        fn component_bounds() {
+            assert_eq!(None, Vector3::<f32>::component_bounds(&[]));
+
+            for &count in &[1, 1000] {
+                let points = random_vectors(count, 3);
+                let mut lo = [f32::INFINITY; 3];
+                let mut hi = [f32::NEG_INFINITY; 3];
+                for p in &points {
+                    for i in 0..3 {
+                        lo[i] = lo[i].min(p[i]);
+                        hi[i] = hi[i].max(p[i]);
+                    }
+                }
+                let expected = (Vector3::from(lo), Vector3::from(hi));
+
+                assert_eq!(Some(expected), Vector3::component_bounds(&points));
+            }
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    mod nalgebra {
+        use super::*;
+
+        #[test]
+        fn vector3_f32() {
+            let v = Vector3::new(1.0f32, -2.0, 3.5);
+            let n: ::nalgebra::Vector3<f32> = v.into();
+
+            assert_eq!(::nalgebra::Vector3::new(1.0, -2.0, 3.5), n);
+            assert_eq!(v, Vector3::from(n));
+        }
+
+        #[test]
+        fn vector3_f64() {
+            let v = Vector3::new(1.0, -2.0, 3.5);
+            let n: ::nalgebra::Vector3<f64> = v.into();
+
+            assert_eq!(::nalgebra::Vector3::new(1.0, -2.0, 3.5), n);
+            assert_eq!(v, Vector3::from(n));
+        }
+
+        #[test]
+        fn round_trip_preserves_bits() {
+            let v = Vector3::new(-0.0f32, f32::MIN_POSITIVE / 4.0, -f32::MAX);
+            let round_trip = Vector3::from(::nalgebra::Vector3::from(v));
+
+            assert_eq!(v.map(f32::to_bits), round_trip.map(f32::to_bits));
+
+            let v = Vector3::new(-0.0f64, f64::MIN_POSITIVE / 4.0, -f64::MAX);
+            let round_trip = Vector3::from(::nalgebra::Vector3::from(v));
+
+            assert_eq!(v.map(f64::to_bits), round_trip.map(f64::to_bits));
+        }
+    }
+
+    mod keys {
+        use super::*;
+        use std::collections::{BTreeMap, HashMap};
+
+        #[test]
+        fn hash_map_key() {
+            let mut map = HashMap::new();
+            map.insert(Vector3::new(1, 2, 3), "first");
+            map.insert(Vector3::new(3, 2, 1), "second");
+            map.insert(Vector3::new(1, 2, 3), "third");
+
+            assert_eq!(2, map.len());
+            assert_eq!(Some(&"third"), map.get(&Vector3::new(1, 2, 3)));
+        }
+
+        #[test]
+        fn voxel_grid() {
+            let voxel_size = 0.5;
+            let points = [
+                Vector3::new(0.1, 0.2, 0.3),
+                Vector3::new(0.4, 0.1, 0.45),
+                Vector3::new(-0.1, 0.2, 0.3),
+                Vector3::new(1e9, -3.2, 7.9),
+            ];
+            let mut grid: HashMap<Vector3<i64>, Vec<usize>> = HashMap::new();
+            for (i, p) in points.iter().enumerate() {
+                let voxel = (*p / voxel_size).floor().to_int().unwrap();
+                grid.entry(voxel).or_default().push(i);
+            }
+
+            assert_eq!(3, grid.len());
+            assert_eq!(Some(&vec![0, 1]), grid.get(&Vector3::new(0, 0, 0)));
+            assert_eq!(Some(&vec![2]), grid.get(&Vector3::new(-1, 0, 0)));
+            assert_eq!(Some(&vec![3]), grid.get(&Vector3::new(2_000_000_000, -7, 15)));
+            assert_eq!(None, grid.get(&Vector3::new(0, 0, 1)));
+        }
+
+        #[test]
+        fn btree_map_key() {
+            let mut map = BTreeMap::new();
+            map.insert(Vector3::new(0, 5, 0), 'b');
+            map.insert(Vector3::new(-1, 9, 9), 'a');
+            map.insert(Vector3::new(0, 5, 1), 'c');
+
+            assert_eq!(vec!['a', 'b', 'c'], map.values().copied().collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn lexicographic_order() {
+            let mut vectors = vec![
+                Vector3::new(1, 0, 0),
+                Vector3::new(0, 2, -1),
+                Vector3::new(0, 2, -3),
+                Vector3::new(-1, 5, 5),
+                Vector3::new(0, -2, 7),
+            ];
+            vectors.sort();
+
+            assert_eq!(vec![
+                Vector3::new(-1, 5, 5),
+                Vector3::new(0, -2, 7),
+                Vector3::new(0, 2, -3),
+                Vector3::new(0, 2, -1),
+                Vector3::new(1, 0, 0),
+            ], vectors);
+        }
+    }
+
+    #[test]
+    fn index() {
+        let v = Vector3::new(1, 2, 3);
+
+        assert_eq!((1, 2, 3), (v[0], v[1], v[2]));
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut v = Vector3::new(1, 2, 3);
+
+        v[0] = 4;
+        v[1] += 3;
+        v[2] *= 2;
+
+        assert_eq!(Vector3::new(4, 5, 6), v);
+    }
+
+    #[test]
+    fn index_in_axis_loop() {
+        let mut v = Vector3::new(1, 2, 3);
+        let delta = Vector3::new(-4, 0, 2);
+
+        for i in 0..3 {
+            v[i] += delta[i];
+        }
+
+        assert_eq!(Vector3::new(-3, 2, 5), v);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Vector3: 3")]
+    fn index_out_of_bounds() {
+        let v = Vector3::new(1, 2, 3);
+
+        let _ = v[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Vector3: 3")]
+    fn index_mut_out_of_bounds() {
+        let mut v = Vector3::new(1, 2, 3);
+
+        v[3] = 4;
+    }
+
+    #[test]
+    fn permute() {
+        let v = Vector3::new(1, 2, 3);
+
+        assert_eq!(v, v.permute(0, 1, 2));
+        assert_eq!(Vector3::new(3, 1, 2), v.permute(2, 0, 1));
+        assert_eq!(Vector3::new(2, 2, 1), v.permute(1, 1, 0));
+    }
+
+    #[test]
+    fn permute_full_cycle() {
+        let v = Vector3::new(1, 2, 3);
+        let once = v.permute(2, 0, 1);
+        let twice = once.permute(2, 0, 1);
+
+        assert_eq!(Vector3::new(2, 3, 1), twice);
+        assert_eq!(v, twice.permute(2, 0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds for Vector3: 5")]
+    fn permute_out_of_bounds() {
+        Vector3::new(1, 2, 3).permute(5, 0, 1);
+    }
+
+    #[test]
+    fn swizzles() {
+        let v = Vector3::new(1, 2, 3);
+
+        assert_eq!(Vector2::new(1, 2), v.xy());
+        assert_eq!(Vector2::new(1, 3), v.xz());
+        assert_eq!(Vector2::new(2, 3), v.yz());
+        assert_eq!(Vector2::new(2, 1), v.yx());
+        assert_eq!(Vector2::new(3, 1), v.zx());
+        assert_eq!(Vector2::new(3, 2), v.zy());
+    }
+
+    #[test]
+    fn from_xy() {
+        let v = Vector3::from_xy(Vector2::new(0.5, -1.5), 2.0);
+
+        assert_eq!(Vector3::new(0.5, -1.5, 2.0), v);
+        assert_eq!(v, Vector3::from_xy(v.xy(), v.z));
+    }
+
+    #[test]
+    fn with_component() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(Vector3::new(-1.0, 2.0, 3.0), v.with_x(-1.0));
+        assert_eq!(Vector3::new(1.0, -2.0, 3.0), v.with_y(-2.0));
+        assert_eq!(Vector3::new(1.0, 2.0, -3.0), v.with_z(-3.0));
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), v);
+    }
+
+    #[test]
+    fn index_max_dimension() {
+        let v = Vector3::new(-4.0, 7.5, 2.0);
+
+        assert_eq!(7.5, v[v.max_dimension()]);
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn neg() {
+            let v = Vector3::new(-1, 2, -3);
+            let expected = Vector3::new(1, -2, 3);
+
+            assert_eq!(expected, -v);
+        }
+
+        #[test]
+        fn add_vectors() {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(-3, 0, 5);
+            let expected = Vector3::new(-2, 2, 8);
+
+            assert_eq!(expected, v1 + v2);
+        }
+
+        #[test]
+        fn add_assign_vectors() {
+            let mut v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(-3, 0, 5);
+            let expected = v1 + v2;
+
+            v1 += v2;
+
+            assert_eq!(expected, v1);
+        }
+
+        #[test]
+        fn sub_vectors() {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(-3, 0, 5);
+            let expected = Vector3::new(4, 2, -2);
+
+            assert_eq!(expected, v1 - v2);
+        }
+
+        #[test]
+        fn sub_assign_vectors() {
+            let mut v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(-3, 0, 5);
+            let expected = Vector3::new(4, 2, -2);
+
+            v1 -= v2;
+
+            assert_eq!(expected, v1);
+        }
+
+        #[test]
+        fn mul_vector_and_scalar() {
+            let v = Vector3::new(1, 2, 3);
+            let expected = Vector3::new(-2, -4, -6);
+
+            assert_eq!(expected, v * -2);
+
+            let v = Vector3::new(1.0, 2.0, 3.0);
+            let expected = Vector3::new(-2.5, -5.0, -7.5);
+
+            assert_eq!(expected, v * -2.5);
+        }
+
+        #[test]
+        fn mul_scalar_and_vector() {
+            let v = Vector3::new(1, 2, 3);
+
+            assert_eq!(v * -2, -2 * v);
+
+            let v = Vector3::new(1u8, 2, 3);
+
+            assert_eq!(v * 4, 4 * v);
+
+            let v = Vector3::new(1.0f32, 2.0, 3.0);
+
+            assert_eq!(v * -2.5, -2.5 * v);
+
+            let v = Vector3::new(1.0, 2.0, 3.0);
+            let expected = Vector3::new(-2.5, -5.0, -7.5);
+
+            assert_eq!(expected, -2.5 * v);
+        }
+
+        #[test]
+        fn mul_scalar_and_vector_64_bit() {
+            let v: Vector3<i64> = Vector3::new(1 << 40, -3, 0);
+            let expected = Vector3::new(3 << 40, -9, 0);
+
+            assert_eq!(expected, 3i64 * v);
+
+            let v: Vector3<f64> = Vector3::new(0.5, -1.25, 1e300);
+            let expected = Vector3::new(1.0, -2.5, 2e300);
+
+            assert_eq!(expected, 2.0f64 * v);
+        }
+
+        #[test]
+        fn mul_assign_vector_and_scalar() {
+            let mut v = Vector3::new(1, 2, 3);
+            let expected = v * -2;
+
+            v *= -2;
+
+            assert_eq!(expected, v);
+
+            let mut v = Vector3::new(1.0, 2.0, 3.0);
+            let expected = v * -2.5;
+
+            v *= -2.5;
+
+            assert_eq!(expected, v);
+        }
+
+        #[test]
+        fn div_vector_and_scalar() {
+            let v = Vector3::new(-2, -4, -6);
+            let expected = Vector3::new(1, 2, 3);
+
+            assert_eq!(expected, v / -2);
+
+            let v = Vector3::new(-2.5, -5.0, -7.5);
+            let expected = Vector3::new(1.0, 2.0, 3.0);
+
+            assert_eq!(expected, v / -2.5);
+        }
+
+        #[test]
+        fn div_assign_vector_and_scalar() {
+            let mut v = Vector3::new(-2, -4, -6);
+            let expected = v / -2;
+
+            v /= -2;
+
+            assert_eq!(expected, v);
+
+            let mut v = Vector3::new(-2.5, -5.0, -7.5);
+            let expected = v / -2.5;
+
+            v /= -2.5;
+
+            assert_eq!(expected, v);
+        }
+
+        #[test]
+        fn dot_product() {
+            let v1 = Vector3::new(1, -2, -3);
+            let v2 = Vector3::new(2, 4, -6);
+            let expected = 1 * 2 + -2 * 4 + -3 * -6;
+
+            assert_eq!(expected, v1.dot(v2));
+        }
+
+        #[test]
+        fn cross_product() {
+            let v1 = Vector3::new(1, -2, -3);
+            let v2 = Vector3::new(2, 4, -6);
+            let expected = Vector3::new(-2 * -6 - -3 * 4, -3 * 2 - 1 * -6, 1 * 4 - -2 * 2);
+
+            assert_eq!(expected, v1.cross(v2));
+        }
+
+        #[test]
+        fn cross_product_ratio() {
+            use num::rational::Ratio;
+
+            let v1 = Vector3::new(Ratio::new(1, 2), Ratio::new(0, 1), Ratio::new(0, 1));
+            let v2 = Vector3::new(Ratio::new(0, 1), Ratio::new(2, 3), Ratio::new(0, 1));
+            let expected = Vector3::new(Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(1, 3));
+
+            assert_eq!(expected, v1.cross(v2));
+        }
+
+        #[test]
+        fn scalar_triple_product() {
+            let a = Vector3::new(1, -2, 3);
+            let b = Vector3::new(4, 0, -1);
+            let c = Vector3::new(-2, 5, 2);
+            let determinant = a.x * (b.y * c.z - b.z * c.y) - a.y * (b.x * c.z - b.z * c.x)
+                + a.z * (b.x * c.y - b.y * c.x);
+
+            assert_eq!(determinant, a.scalar_triple(b, c));
+            assert_eq!(determinant, b.scalar_triple(c, a));
+            assert_eq!(-determinant, b.scalar_triple(a, c));
+            assert_eq!(0, a.scalar_triple(b, a + b));
+        }
+
+        #[test]
+        fn cross_product_nearly_parallel() {
+            let v1 = Vector3::new(1.0, 1.0 + f32::EPSILON, 1.0);
+            let v2 = Vector3::new(1.0 + f32::EPSILON, 1.0, 1.0 - f32::EPSILON);
+            let expected = v1.cast::<f64>().unwrap().cross(v2.cast().unwrap());
+            let naive_x = v1.y * v2.z - v1.z * v2.y;
+
+            let cross = v1.cross(v2);
+
+            assert_eq!(0.0, naive_x);
+            assert_ne!(0.0, expected.x);
+            for i in 0..3 {
+                let error = (cross[i] as f64 - expected[i]).abs();
+                assert!(error <= 2.0 * f32::EPSILON as f64 * expected[i].abs());
+            }
+        }
+
+        #[test]
+        fn mul_add() {
+            let v = Vector3::new(1.0, -2.0, 3.0);
+            let a = Vector3::new(0.5, 4.0, -2.0);
+            let b = Vector3::new(1.0, 1.0, 1.0);
+
+            assert_eq!(v.mul_componentwise(a) + b, v.mul_add(a, b));
+        }
+
+        #[test]
+        fn dot_product_fma() {
+            let v1 = Vector3::new(1.0, -2.0, -3.0);
+            let v2 = Vector3::new(2.0, 4.0, -6.0);
+
+            assert_eq!(v1.dot(v2), v1.dot_fma(v2));
+
+            let a = 1.0 + f64::EPSILON;
+            let v1 = Vector3::new(a, -1.0, 0.0);
+            let v2 = Vector3::new(a, 1.0, 0.0);
+
+            assert_eq!(2.0 * f64::EPSILON + f64::EPSILON * f64::EPSILON, v1.dot_fma(v2));
+        }
+
+        #[test]
+        fn length_squared() {
+            let v = Vector3::new(1, -2, -3);
+            let expected = 1 * 1 + -2 * -2 + -3 * -3;
+
+            assert_eq!(expected, v.length_squared());
+
+            let v = Vector3::new(1.0, -2.0, -3.0);
+            let expected = (1 * 1 + -2 * -2 + -3 * -3) as f64;
+
+            assert_eq!(expected, v.length_squared());
+
+            let v = Vector3::new(1.0f32, -2.0, -3.0);
+
+            assert_eq!(14.0f32, v.length_squared());
+        }
+
+        #[test]
+        fn length_squared_billion_scale_integers() {
+            let v = Vector3::new(1_000_000_007i64, -999_999_937, 1_000_000_009);
+            let expected: i128 = [v.x, v.y, v.z].iter().map(|&c| c as i128 * c as i128).sum();
+
+            assert_eq!(expected, v.length_squared() as i128);
+            assert_ne!(expected, (v.length_squared() as f64) as i128);
+        }
+
+        #[test]
+        fn length_squared_keeps_component_type() {
+            let v = Vector3::new(0.1f32, 0.2, 0.3);
+            let length_squared: f32 = v.length_squared();
+
+            assert_eq!(0.1f32 * 0.1 + 0.2 * 0.2 + 0.3 * 0.3, length_squared);
+
+            let v = Vector3::new(0.1f64, 0.2, 0.3);
+            let length_squared: f64 = v.length_squared();
+
+            assert_eq!(0.1f64 * 0.1 + 0.2 * 0.2 + 0.3 * 0.3, length_squared);
+        }
+
+        #[test]
+        fn length_squared_large_integers() {
+            let v = Vector3::new(1i64 << 27, (1 << 27) + 1, 0);
+            let expected = (1i64 << 55) + (1 << 28) + 1;
+
+            assert!(expected > 1 << 53);
+            assert_eq!(expected, v.length_squared());
+        }
+
+        #[test]
+        fn length() {
+            let v = Vector3::new(1.0, -2.0, -3.0);
+            let expected = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
+
+            assert_eq!(expected, v.length());
+
+            let v = Vector3::new(2.0f32, -3.0, 6.0);
+
+            assert_eq!(7.0f32, v.length());
+        }
+
+        #[test]
+        fn length_f64() {
+            let v = Vector3::new(1, -2, -3);
+            let expected = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
+
+            assert_eq!(expected, v.length_f64());
+            assert_eq!(7.0, Vector3::new(2u8, 3, 6).length_f64());
+            assert_eq!(7.0, Vector3::new(2.0f32, -3.0, 6.0).length_f64());
+            assert_eq!(v.cast::<f64>().unwrap().length(), v.length_f64());
+        }
+
+        #[test]
+        fn cast_int_to_float() {
+            let v = Vector3::new(1i32, -2, 3);
+
+            assert_eq!(Some(Vector3::new(1.0, -2.0, 3.0)), v.cast::<f64>());
+            assert_eq!(Some(Vector3::new(1.0f32, -2.0, 3.0)), v.cast::<f32>());
+        }
+
+        #[test]
+        fn cast_float_to_int() {
+            let v = Vector3::new(1.9, -2.9, 0.5);
+
+            assert_eq!(Some(Vector3::new(1, -2, 0)), v.cast::<i32>());
+        }
+
+        #[test]
+        fn cast_between_ints() {
+            assert_eq!(Some(Vector3::new(1i64, -2, 3)), Vector3::new(1i8, -2, 3).cast());
+            assert_eq!(Some(Vector3::new(1u8, 2, 255)), Vector3::new(1i32, 2, 255).cast());
+        }
+
+        #[test]
+        fn cast_out_of_range() {
+            assert_eq!(None, Vector3::new(1e300, 0.0, 0.0).cast::<i32>());
+            assert_eq!(None, Vector3::new(0, 256, 0).cast::<u8>());
+            assert_eq!(None, Vector3::new(0, 0, -1).cast::<u32>());
+            assert_eq!(None, Vector3::new(0.0, f64::INFINITY, 0.0).cast::<i64>());
+        }
+
+        #[test]
+        fn distance() {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(4, -2, 3);
+
+            assert_eq!(25, v1.distance_squared(v2));
+
+            let v1 = Vector3::new(1.0, 2.0, 3.0);
+            let v2 = Vector3::new(4.0, -2.0, 3.0);
+
+            assert_eq!(25.0, v1.distance_squared(v2));
+            assert_eq!(5.0, v1.distance(v2));
+            assert_eq!(v1.distance(v2), v2.distance(v1));
+
+            let v1 = Vector3::new(0.0, 0.0, 1.5);
+            let v2 = Vector3::new(0.0, 3.0, -2.5);
+
+            assert_eq!(5.0, v1.distance(v2));
+        }
+
+        #[test]
+        fn distance_f64() {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(4, -2, 3);
+
+            assert_eq!(5.0, v1.distance_f64(v2));
+            assert_eq!(v1.distance_f64(v2), v2.distance_f64(v1));
+
+            let v1 = Vector3::new(1.0f32, 2.0, 3.0);
+            let v2 = Vector3::new(4.0f32, -2.0, 3.0);
+
+            assert_eq!(5.0, v1.distance_f64(v2));
+        }
+
+        #[test]
+        fn distance_f64_large_integers() {
+            // Subtracting or squaring these in i64 would overflow.
+            let v1 = Vector3::new(i64::MIN, 0, i64::MIN);
+            let v2 = Vector3::new(i64::MAX, 0, i64::MAX);
+            let expected = 2f64.powi(64);
+
+            assert_eq!(2.0 * expected * expected, v1.distance_squared_f64(v2));
+            assert_eq!(expected * 2f64.sqrt(), v1.distance_f64(v2));
+            assert_eq!(v1.distance_f64(v2), v2.distance_f64(v1));
+        }
+
+        #[test]
+        fn manhattan_and_chebyshev_distance() {
+            let v1 = Vector3::new(1, -7, 3);
+            let v2 = Vector3::new(-4, 2, 5);
+
+            assert_eq!(5 + 9 + 2, v1.manhattan_distance(v2));
+            assert_eq!(9, v1.chebyshev_distance(v2));
+            assert_eq!(v1.manhattan_distance(v2), v2.manhattan_distance(v1));
+            assert_eq!(v1.chebyshev_distance(v2), v2.chebyshev_distance(v1));
+            assert_eq!(0, v1.manhattan_distance(v1));
+            assert_eq!(0, v1.chebyshev_distance(v1));
+
+            let v1 = Vector3::new(0.5, 1.0, -2.0);
+            let v2 = Vector3::new(-1.0, 1.0, 0.5);
+
+            assert_eq!(4.0, v1.manhattan_distance(v2));
+            assert_eq!(2.5, v1.chebyshev_distance(v2));
+        }
+
+        #[test]
+        fn distance_squared_large_integers() {
+            let v1 = Vector3::new(-(1i64 << 26), 5, 0);
+            let v2 = Vector3::new(1i64 << 26, 5, 1);
+            let expected = (1i64 << 54) + 1;
+
+            assert!(expected > 1 << 53);
+            assert_eq!(expected, v1.distance_squared(v2));
+            assert_eq!(expected, v2.distance_squared(v1));
+        }
+
+        #[test]
+        fn normalize() {
+            let v = Vector3::new(1, -2, -3);
+            let length = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
+            let expected = Vector3::new(1.0 / length, -2.0 / length, -3.0 / length);
+
+            assert_eq!(expected, v.normalize());
+        }
+
+        #[test]
+        fn normalize_has_unit_length() {
+            let v = Vector3::new(3, -7, 12);
+            assert!((v.normalize().length() - 1.0).abs() < 1e-12);
+
+            let v = Vector3::new(1e-3, 2e5, -4.5);
+            assert!((v.normalize().length() - 1.0).abs() < 1e-12);
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn normalize_zero_vector_is_nan() {
+            let v = Vector3::new(0, 0, 0).normalize();
+
+            assert!(v.x.is_nan() && v.y.is_nan() && v.z.is_nan());
+        }
+
+        #[test]
+        fn normalize_or_zero() {
+            let v = Vector3::new(0.0, 0.0, 0.0);
+            assert_eq!(Vector3::new(0.0, 0.0, 0.0), v.normalize_or_zero());
+
+            let v = Vector3::new(1, -2, -3);
+            assert_eq!(v.normalize(), v.normalize_or_zero());
+        }
+
+        #[test]
+        fn try_normalize_eps() {
+            let v = Vector3::new(3.0, 0.0, -4.0);
+
+            assert_eq!(Some(Vector3::new(0.6, 0.0, -0.8)), v.try_normalize_eps(1e-12));
+            assert_eq!(None, v.try_normalize_eps(5.0));
+            assert_eq!(None, Vector3::new(0, 0, 0).try_normalize_eps(0.0));
+
+            let tiny = Vector3::new(0.0, 1e-200, 0.0);
+
+            assert_eq!(None, tiny.try_normalize_eps(1e-12));
+            assert_eq!(None, tiny.try_normalize());
+
+            let small = Vector3::new(0.0, 1e-100, 0.0);
+
+            assert_eq!(Some(Vector3::new(0.0, 1.0, 0.0)), small.try_normalize());
+        }
+
+        #[test]
+        fn normalize_or() {
+            let fallback = Vector3::new(0.0, 1.0, 0.0);
+
+            assert_eq!(Vector3::new(0.0, 0.0, -1.0), Vector3::new(0, 0, -7).normalize_or(fallback));
+            assert_eq!(fallback, Vector3::new(0, 0, 0).normalize_or(fallback));
+            assert_eq!(fallback, Vector3::new(1e-170, 0.0, -1e-170).normalize_or(fallback));
+            assert_eq!(fallback, Vector3::new(f64::MIN_POSITIVE, 0.0, 0.0).normalize_or(fallback));
+        }
+
+        #[test]
+        fn is_normalized() {
+            let v = Vector3::new(0.3, -1.2, 2.5).normalize();
+
+            assert!(v.is_normalized());
+            assert!((v * (1.0 + 1e-7)).is_normalized());
+            assert!(!(v * (1.0 + 1e-5)).is_normalized());
+            assert!(Vector3::new(0, -1, 0).is_normalized());
+            assert!(!Vector3::new(0, 0, 0).is_normalized());
+        }
+
+        #[test]
+        fn rotate_about_axis_quarter_turns() {
+            use std::f64::consts::FRAC_PI_2;
+
+            let x = Vector3::new(1, 0, 0);
+            let y = Vector3::new(0, 1, 0);
+            let z = Vector3::new(0, 0, 1);
+            let cases = [(x, y, z), (y, z, x), (z, x, y)];
+            for &(axis, from, to) in &cases {
+                let axis = axis.cast().unwrap();
+                let rotated = from.rotate_about_axis(axis, FRAC_PI_2);
+
+                assert!(rotated.abs_diff_eq(to.cast().unwrap(), 1e-15), "got {:?}", rotated);
+                assert_eq!(axis, axis.rotate_about_axis(axis, FRAC_PI_2));
+            }
+        }
+
+        #[test]
+        fn rotate_about_axis_full_turn() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let axis = Vector3::new(1.0, 2.0, -0.5).normalize();
+
+            let rotated = v.rotate_about_axis(axis, 2.0 * std::f64::consts::PI);
+            assert!(rotated.abs_diff_eq(v, 1e-14), "got {:?}", rotated);
+        }
+
+        #[test]
+        fn rotate_about_axis_zero_angle() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let axis = Vector3::new(1.0, 2.0, -0.5).normalize();
+
+            assert_eq!(v, v.rotate_about_axis(axis, 0.0));
+            assert_eq!(v, v.rotate_about_axis(axis, -0.0));
+        }
+
+        #[test]
+        fn rotate_about_axis_composes() {
+            use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let axis = Vector3::new(-2.0, 0.5, 1.0).normalize();
+            let twice = v.rotate_about_axis(axis, FRAC_PI_4).rotate_about_axis(axis, FRAC_PI_4);
+            let once = v.rotate_about_axis(axis, FRAC_PI_2);
+
+            assert!(twice.abs_diff_eq(once, 1e-14), "expected {:?}, got {:?}", once, twice);
+            assert!((v.length() - once.length()).abs() < 1e-14);
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Rotation axis isn't normalized"))]
+        fn rotate_about_unnormalized_axis() {
+            Vector3::new(1.0, 0.0, 0.0).rotate_about_axis(Vector3::new(0.0, 0.0, 2.0), 1.0);
+        }
+
+        #[test]
+        fn try_normalize() {
+            let v = Vector3::new(0.0, 0.0, 0.0);
+            assert_eq!(None, v.try_normalize());
+
+            let v = Vector3::new(1, -2, -3);
+            assert_eq!(Some(v.normalize()), v.try_normalize());
+        }
+
+        #[test]
+        fn coordinate_system_f32() {
+            let v1 = Vector3::new(0.3f32, -0.4, 2.0).normalize();
+            let v1 = Vector3::new(v1.x as f32, v1.y as f32, v1.z as f32);
+            let (v2, v3) = v1.coordinate_system();
+
+            assert!(v1.dot(v2).abs() < 1e-6);
+            assert!(v1.dot(v3).abs() < 1e-6);
+            assert!(v2.dot(v3).abs() < 1e-6);
+            assert!((v2.length() - 1.0).abs() < 1e-6);
+            assert!((v3.length() - 1.0).abs() < 1e-6);
+        }
+
+        #[test]
+        fn face_forward() {
+            let v = Vector3::new(1.0, -2.0, 0.5);
+
+            assert_eq!(v, v.face_forward(Vector3::new(1.0, 0.0, 0.0)));
+            assert_eq!(-v, v.face_forward(Vector3::new(0.0, 1.0, 0.0)));
+            assert_eq!(v, v.face_forward(Vector3::new(2.0, 1.0, 0.0)));
+        }
+
+        #[test]
+        fn face_forward_negative_zero_dot() {
+            let v = Vector3::new(1.0, -1.0, 0.0);
+            let other = Vector3::new(-0.0, 0.0, -0.0);
+
+            assert!(v.dot(other).is_sign_negative());
+            assert_eq!(v, v.face_forward(other));
+        }
+
+        #[test]
+        fn map() {
+            let v = Vector3::new(1, -2, 3);
+
+            assert_eq!(Vector3::new(2, -4, 6), v.map(|c| c * 2));
+            assert_eq!(Vector3::new(0.5, -1.0, 1.5), v.map(|c| c as f64 / 2.0));
+        }
+
+        #[test]
+        fn map_calls_in_order() {
+            let mut calls = Vec::new();
+            Vector3::new(1, 2, 3).map(|c| {
+                calls.push(c);
+                c
+            });
+
+            assert_eq!(vec![1, 2, 3], calls);
+        }
+
+        #[test]
+        fn map_changes_type() {
+            let v = Vector3::new(1.6, -2.2, 3.0);
+
+            assert_eq!(Vector3::new(2, -2, 3), v.map(|c| c.round() as i32));
+        }
+
+        #[test]
+        fn zip_with_changes_type() {
+            let v1 = Vector3::new(1.6, -2.2, 3.0);
+            let v2 = Vector3::new(2, 3, -1);
+
+            assert_eq!(Vector3::new(3, 0, 2), v1.zip_with(v2, |a, b| (a + b as f64) as i32));
+        }
+
+        #[test]
+        fn zip_with() {
+            let v1 = Vector3::new(1, -2, 3);
+            let v2 = Vector3::new(4, 5, -6);
+
+            assert_eq!(Vector3::new(4, 5, 3), v1.zip_with(v2, std::cmp::max));
+            assert_eq!(Vector3::new(0.25, -0.4, -0.5), v1.zip_with(v2, |a, b| a as f64 / b as f64));
+        }
+
+        #[test]
+        fn mul_componentwise() {
+            let v1 = Vector3::new(1, -2, 3);
+            let v2 = Vector3::new(-3, 0, 5);
+
+            assert_eq!(Vector3::new(-3, 0, 15), v1.mul_componentwise(v2));
+
+            let v1 = Vector3::new(1.5, -2.0, 3.0);
+            let v2 = Vector3::new(-3.0, 0.0, 0.5);
+
+            assert_eq!(Vector3::new(-4.5, -0.0, 1.5), v1.mul_componentwise(v2));
+        }
+
+        #[test]
+        fn div_componentwise() {
+            let v1 = Vector3::new(-3, 0, 15);
+            let v2 = Vector3::new(-3, 2, 5);
+
+            assert_eq!(Vector3::new(1, 0, 3), v1.div_componentwise(v2));
+
+            let v1 = Vector3::new(-4.5, 1.0, 1.5);
+            let v2 = Vector3::new(-3.0, 4.0, 0.5);
+
+            assert_eq!(Vector3::new(1.5, 0.25, 3.0), v1.div_componentwise(v2));
+        }
+
+        #[test]
+        fn div_componentwise_fractional() {
+            let v1 = Vector3::new(1.0, 2.0, -7.0);
+            let v2 = Vector3::new(3.0, 3.0, 2.0);
+
+            assert_eq!(Vector3::new(1.0 / 3.0, 2.0 / 3.0, -3.5), v1.div_componentwise(v2));
+
+            let v1 = Vector3::new(1, 2, -7);
+            let v2 = Vector3::new(3, 3, 2);
+
+            assert_eq!(Vector3::new(0, 0, -3), v1.div_componentwise(v2));
+        }
+
+        #[test]
+        #[should_panic]
+        fn div_componentwise_by_zero_integer() {
+            let v1 = Vector3::new(1, 2, 3);
+            let v2 = Vector3::new(1, 0, 1);
+
+            v1.div_componentwise(v2);
+        }
+
+        #[test]
+        fn div_componentwise_by_zero_float() {
+            let v1 = Vector3::new(1.0, -2.0, 0.5);
+            let v2 = Vector3::new(0.0, 0.0, -0.0);
+            let v = v1.div_componentwise(v2);
+
+            assert_eq!(Vector3::new(f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY), v);
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn div_componentwise_zero_by_zero_float() {
+            let v1 = Vector3::new(1.0, -2.0, 0.0);
+            let v2 = Vector3::new(1.0, 1.0, 0.0);
+            let v = v1.div_componentwise(v2);
+
+            assert!(v.z.is_nan());
+        }
+
+        #[test]
+        fn checked_div() {
+            let v = Vector3::new(3, -6, 9);
+
+            assert_eq!(Some(Vector3::new(1, -2, 3)), v.checked_div(3));
+            assert_eq!(None, v.checked_div(0));
+
+            let v = Vector3::new(1.0, -2.0, 0.0);
+
+            assert_eq!(Some(Vector3::new(0.25, -0.5, 0.0)), v.checked_div(4.0));
+            assert_eq!(None, v.checked_div(0.0));
+            assert_eq!(None, v.checked_div(-0.0));
+        }
+
+        #[test]
+        fn checked_div_componentwise() {
+            let v1 = Vector3::new(3, -6, 9);
+            let v2 = Vector3::new(1, -3, -3);
+
+            assert_eq!(Some(Vector3::new(3, 2, -3)), v1.checked_div_componentwise(v2));
+            assert_eq!(None, v1.checked_div_componentwise(Vector3::new(1, 0, -3)));
+            assert_eq!(None, v1.checked_div_componentwise(Vector3::zero()));
+
+            let v1 = Vector3::new(1.0, -2.0, 0.0);
+            let v2 = Vector3::new(0.5, -2.0, 1.0);
+
+            assert_eq!(Some(Vector3::new(2.0, 1.0, 0.0)), v1.checked_div_componentwise(v2));
+            assert_eq!(None, v1.checked_div_componentwise(Vector3::new(1.0, 1.0, 0.0)));
+        }
+
+        #[test]
+        fn lerp() {
+            let v1 = Vector3::new(0.1, -2.0, 7.3);
+            let v2 = Vector3::new(1.7, 4.0, -0.9);
+
+            assert_eq!(v1, v1.lerp(v2, 0.0));
+            assert_eq!(v2, v1.lerp(v2, 1.0));
+
+            let v1 = Vector3::new(0.5, -2.0, 7.0);
+            let v2 = Vector3::new(1.5, 4.0, -1.0);
+
+            assert_eq!(Vector3::new(1.0, 1.0, 3.0), v1.lerp(v2, 0.5));
+        }
+
+        #[test]
+        fn lerp_f32() {
+            let v1 = Vector3::new(0.3f32, -2.1, 7.7);
+            let v2 = Vector3::new(1.9f32, 4.4, -0.6);
+
+            assert_eq!(v1, v1.lerp(v2, 0.0));
+            assert_eq!(v2, v1.lerp(v2, 1.0));
+        }
+
+        #[test]
+        fn lerp_extrapolates() {
+            let v1 = Vector3::new(0.0, 2.0, 4.0);
+            let v2 = Vector3::new(1.0, 4.0, 0.0);
+
+            assert_eq!(Vector3::new(2.0, 6.0, -4.0), v1.lerp(v2, 2.0));
+            assert_eq!(Vector3::new(-1.0, 0.0, 8.0), v1.lerp(v2, -1.0));
+        }
+
+        #[test]
+        fn lerp_componentwise() {
+            let v1 = Vector3::new(0.1, -2.0, 7.3);
+            let v2 = Vector3::new(1.7, 4.0, -0.9);
+            let t = Vector3::new(0.0, 0.5, 1.0);
+
+            assert_eq!(Vector3::new(0.1, 1.0, -0.9), v1.lerp_componentwise(v2, t));
+        }
+
+        #[test]
+        fn abs_diff_eq() {
+            let v = Vector3::new(1.0, -2.0, 3.0);
+
+            assert!(v.abs_diff_eq(v, 0.0));
+            assert!(v.abs_diff_eq(Vector3::new(1.0005, -2.0, 2.9995), 1e-3));
+            assert!(!v.abs_diff_eq(Vector3::new(1.0, -2.002, 3.0), 1e-3));
+        }
+
+        #[test]
+        fn relative_eq() {
+            let v = Vector3::new(1e12, -2e-12, 3.0);
+
+            assert!(v.relative_eq(v, 0.0, 0.0));
+            assert!(v.relative_eq(Vector3::new(1e12 + 1.0, -2.000001e-12, 3.0), 0.0, 1e-6));
+            assert!(!v.relative_eq(Vector3::new(1e12, -2.1e-12, 3.0), 0.0, 1e-6));
+            assert!(v.relative_eq(Vector3::new(1e12, 0.0, 3.0), 1e-11, 1e-6));
+        }
+
+        #[test]
+        fn relative_eq_one_ulp() {
+            let next_up = |x: f64| f64::from_bits(x.to_bits() + 1);
+            let v1 = Vector3::new(0.1, 1e300, 7.0);
+            let v2 = Vector3::new(next_up(0.1), next_up(1e300), next_up(7.0));
+
+            assert_ne!(v1, v2);
+            assert!(v1.relative_eq(v2, 0.0, f64::EPSILON));
+            assert!(!v1.relative_eq(v2 * 1.000001, 0.0, f64::EPSILON));
+
+            let next_up = |x: f32| f32::from_bits(x.to_bits() + 1);
+            let v1 = Vector3::new(0.1f32, 1e30, 7.0);
+            let v2 = Vector3::new(next_up(0.1), next_up(1e30), next_up(7.0));
+
+            assert_ne!(v1, v2);
+            assert!(v1.relative_eq(v2, 0.0, f32::EPSILON));
+        }
+
+        #[test]
+        fn relative_eq_infinity() {
+            let v = Vector3::new(f64::INFINITY, 0.0, 0.0);
+
+            assert!(v.relative_eq(v, 0.0, 1e-6));
+            assert!(!v.relative_eq(-v, 0.0, 1e-6));
+            assert!(!v.relative_eq(Vector3::new(f64::MAX, 0.0, 0.0), 0.0, 1e-6));
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn new_with_nan() {
+            let v = Vector3::new(1.0, f64::NAN, 3.0);
+
+            assert!(v.is_nan());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn div_zero_vector_by_zero() {
+            let v = Vector3::new(0.0, 0.0, 0.0) / 0.0;
+
+            assert!(v.is_nan());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn add_opposite_infinities() {
+            let v1 = Vector3::new(f64::INFINITY, 0.0, 0.0);
+            let v2 = Vector3::new(f64::NEG_INFINITY, 0.0, 0.0);
+
+            assert!((v1 + v2).is_nan());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Dot product is NaN"))]
+        fn dot_nan() {
+            let v1 = Vector3::new(f64::INFINITY, 0.0, 0.0);
+            let v2 = Vector3::new(0.0, 1.0, 0.0);
+
+            assert!(v1.dot(v2).is_nan());
+        }
+
+        #[test]
+        fn div_by_zero_is_infinite() {
+            let v = Vector3::new(1.0, -2.0, 3.0) / 0.0;
+
+            assert_eq!(Vector3::new(f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY), v);
+        }
+
+        #[test]
+        fn is_nan() {
+            assert!(!Vector3::new(1.0, -2.0, 3.0).is_nan());
+            assert!(Vector3 { x: f64::NAN, y: -2.0, z: 3.0 }.is_nan());
+            assert!(Vector3 { x: 1.0, y: f32::NAN, z: 3.0 }.is_nan());
+            assert!(Vector3 { x: 1.0, y: -2.0, z: f64::NAN }.is_nan());
+            assert!(!Vector3::new(f64::INFINITY, f64::NEG_INFINITY, 3.0).is_nan());
+        }
+
+        #[test]
+        fn has_nans() {
+            assert!(!Vector3::new(1.0, -2.0, f64::INFINITY).has_nans());
+            assert!(Vector3 { x: 1.0, y: -2.0, z: f64::NAN }.has_nans());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn cross_nan() {
+            let v1 = Vector3::new(f64::INFINITY, 0.0, 0.0);
+            let v2 = Vector3::new(0.0, 1.0, 0.0);
+
+            assert!(v1.cross(v2).has_nans());
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn mul_add_nan() {
+            let v = Vector3::new(f64::INFINITY, 0.0, 0.0);
+
+            assert!(v.mul_add(Vector3::zero(), Vector3::zero()).has_nans());
+        }
+
+        #[test]
+        fn is_finite() {
+            assert!(Vector3::new(1.0, -2.0, 3.0).is_finite());
+            assert!(!Vector3::new(f64::INFINITY, -2.0, 3.0).is_finite());
+            assert!(!Vector3::new(1.0, f32::NEG_INFINITY, 3.0).is_finite());
+            assert!(!Vector3 { x: 1.0, y: -2.0, z: f64::NAN }.is_finite());
+        }
+
+        #[test]
+        fn floor() {
+            let v = Vector3::new(-0.5, 1.5, -2.0);
+
+            assert_eq!(Vector3::new(-1.0, 1.0, -2.0), v.floor());
+        }
+
+        #[test]
+        fn ceil() {
+            let v = Vector3::new(-0.5, 1.5, -2.0);
+
+            assert_eq!(Vector3::new(-0.0, 2.0, -2.0), v.ceil());
+        }
+
+        #[test]
+        fn round() {
+            let v = Vector3::new(-0.5, 1.4, -2.0);
+
+            assert_eq!(Vector3::new(-1.0, 1.0, -2.0), v.round());
+
+            let v = Vector3::new(0.5f32, -1.6, 2.5);
+
+            assert_eq!(Vector3::new(1.0, -2.0, 3.0), v.round());
+        }
+
+        #[test]
+        fn floor_and_round_negative() {
+            let v = Vector3::new(-1.4, -1.6, -0.2);
+
+            assert_eq!(Vector3::new(-2.0, -2.0, -1.0), v.floor());
+            assert_eq!(Vector3::new(-1.0, -2.0, -0.0), v.round());
+        }
+
+        #[test]
+        fn trunc() {
+            let v = Vector3::new(-0.5, 1.5, -2.7);
+
+            assert_eq!(Vector3::new(-0.0, 1.0, -2.0), v.trunc());
+        }
+
+        #[test]
+        fn to_int() {
+            let v = Vector3::new(-0.5, 1.5, -2.7);
+
+            assert_eq!(Some(Vector3::new(0, 1, -2)), v.to_int::<i32>());
+            assert_eq!(Some(Vector3::new(-1, 1, -3)), v.floor().to_int::<i64>());
+            assert_eq!(Some(Vector3::new(0, 2, -2)), v.ceil().to_int::<i32>());
+            assert_eq!(None, v.to_int::<u32>());
+        }
+
+        #[test]
+        fn to_int_out_of_range() {
+            assert_eq!(None, Vector3::new(0.0, 1e10, 0.0).to_int::<i32>());
+            assert_eq!(None, Vector3 { x: f64::NAN, y: 0.0, z: 0.0 }.to_int::<i32>());
+            assert_eq!(None, Vector3::new(0.0, 0.0, f64::INFINITY).to_int::<i64>());
+        }
+
+        fn assert_orthonormal_basis(v1: Vector3<f64>) {
+            let (v2, v3) = v1.coordinate_system();
+
+            assert!(v1.dot(v2).abs() < 1e-10, "{:?} and {:?} aren't orthogonal", v1, v2);
+            assert!(v1.dot(v3).abs() < 1e-10, "{:?} and {:?} aren't orthogonal", v1, v3);
+            assert!(v2.dot(v3).abs() < 1e-10, "{:?} and {:?} aren't orthogonal", v2, v3);
+            assert!((v2.length() - 1.0).abs() < 1e-10, "{:?} isn't normalized", v2);
+            assert!((v3.length() - 1.0).abs() < 1e-10, "{:?} isn't normalized", v3);
+            assert!(v1.cross(v2).abs_diff_eq(v3, 1e-10), "basis for {:?} isn't right-handed", v1);
+        }
+
+        #[test]
+        fn coordinate_system_axes() {
+            assert_orthonormal_basis(Vector3::new(1.0, 0.0, 0.0));
+            assert_orthonormal_basis(Vector3::new(-1.0, 0.0, 0.0));
+            assert_orthonormal_basis(Vector3::new(0.0, 1.0, 0.0));
+            assert_orthonormal_basis(Vector3::new(0.0, -1.0, 0.0));
+            assert_orthonormal_basis(Vector3::new(0.0, 0.0, 1.0));
+            assert_orthonormal_basis(Vector3::new(0.0, 0.0, -1.0));
+        }
+
+        #[test]
+        fn coordinate_system_near_axes() {
+            for &epsilon in &[1e-3, 1e-6, 1e-9, 1e-12] {
+                assert_orthonormal_basis(Vector3::new(epsilon, -epsilon, 1.0).normalize());
+                assert_orthonormal_basis(Vector3::new(epsilon, epsilon, -1.0).normalize());
+                assert_orthonormal_basis(Vector3::new(1.0, epsilon, epsilon).normalize());
+                assert_orthonormal_basis(Vector3::new(-epsilon, 1.0, -epsilon).normalize());
+            }
+        }
+
+        #[test]
+        fn coordinate_system_sweep() {
+            for i in 0..=64 {
+                let theta = std::f64::consts::PI * i as f64 / 64.0;
+                for j in 0..128 {
+                    let phi = 2.0 * std::f64::consts::PI * j as f64 / 128.0;
+                    let v = Vector3::new(
+                        theta.sin() * phi.cos(),
+                        theta.sin() * phi.sin(),
+                        theta.cos());
+                    assert_orthonormal_basis(v);
+                }
+            }
+        }
+
+        #[test]
+        fn angle_between() {
+            use std::f64::consts::{FRAC_PI_4, PI};
+
+            let v1 = Vector3::new(1.0, 0.0, 0.0);
+
+            assert_eq!(0.0, v1.angle_between(v1));
+            assert_eq!(PI, v1.angle_between(-v1 * 2.0));
+            assert!((FRAC_PI_4 - v1.angle_between(Vector3::new(3.0, 3.0, 0.0))).abs() < 1e-15);
+        }
+
+        #[test]
+        fn angle_between_small_angles() {
+            let v1 = Vector3::new(1.0, 0.0, 0.0);
+            for &theta in &[1e-4f64, 1e-8, 1e-12] {
+                let v2 = Vector3::new(theta.cos(), theta.sin(), 0.0);
+
+                assert!(((v1.angle_between(v2) - theta) / theta).abs() < 1e-12);
+                let supplement = std::f64::consts::PI - theta;
+                assert!(((v1.angle_between(-v2) - supplement) / theta).abs() < 1e-3);
+            }
+
+            // The naive formulation can't resolve these angles at all.
+            let v2 = Vector3::new(1e-8f64.cos(), 1e-8f64.sin(), 0.0);
+            assert_eq!(0.0, v1.dot(v2).acos());
+        }
+
+        #[test]
+        fn slerp() {
+            use std::f64::consts::FRAC_1_SQRT_2;
+
+            let v1 = Vector3::new(1.0, 0.0, 0.0);
+            let v2 = Vector3::new(0.0, 0.0, 1.0);
+
+            assert_eq!(v1, v1.slerp(v2, 0.0));
+            assert_eq!(v2, v1.slerp(v2, 1.0));
+
+            let midpoint = v1.slerp(v2, 0.5);
+            let expected = Vector3::new(FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2);
+            assert!(midpoint.abs_diff_eq(expected, 1e-15), "got {:?}", midpoint);
+        }
+
+        #[test]
+        fn slerp_constant_angular_speed() {
+            let v1 = Vector3::new(0.3, -1.2, 2.5).normalize();
+            let v2 = Vector3::new(-2.0, 0.5, 1.0).normalize();
+            let theta = v1.angle_between(v2);
+            for i in 0..=10 {
+                let t = i as f64 / 10.0;
+                let v = v1.slerp(v2, t);
+
+                assert!((v.length() - 1.0).abs() < 1e-15);
+                assert!((v1.angle_between(v) - t * theta).abs() < 1e-14);
+                assert!((v.angle_between(v2) - (1.0 - t) * theta).abs() < 1e-14);
+            }
+        }
+
+        #[test]
+        fn slerp_nearly_parallel() {
+            let v1 = Vector3::new(1.0, 0.0, 0.0);
+            let v2 = Vector3::new(1e-10f64.cos(), 1e-10f64.sin(), 0.0);
+            for &v in &[v1.slerp(v2, 0.5), v1.slerp(v1, 0.5), v1.slerp(v1, 0.0)] {
+                assert!(!v.is_nan());
+                assert!((v.length() - 1.0).abs() < 1e-15);
+                assert!(v1.angle_between(v) <= 1e-10);
+            }
+
+            let v = Vector3::new(1.0f32, 0.0, 0.0).slerp(Vector3::new(1.0, 1e-5, 0.0), 0.5);
+            assert!((v.length() - 1.0).abs() <= f32::EPSILON);
+        }
+
+        #[test]
+        fn angle_between_nearly_parallel_f32() {
+            let v1 = Vector3::new(1.0f32, 2.0, 3.0);
+            let v2 = Vector3::new(1.0f32, 2.0 + 4.0 * f32::EPSILON, 3.0);
+            let reference = v1.cast::<f64>().unwrap().angle_between(v2.cast().unwrap());
+
+            assert!(reference > 0.0);
+            assert!(((v1.angle_between(v2) as f64 - reference) / reference).abs() < 1e-5);
+            let antiparallel = std::f64::consts::PI - reference;
+            assert!((v1.angle_between(-v2) as f64 - antiparallel).abs() < f32::EPSILON as f64);
+        }
+
+        #[test]
+        fn spherical_direction_trig_matches_angles() {
+            use std::f64::consts::PI;
+
+            for i in 0..=8 {
+                let theta = PI * i as f64 / 8.0;
+                let (sin_theta, cos_theta) = theta.sin_cos();
+                for j in 0..16 {
+                    let phi = 2.0 * PI * j as f64 / 16.0;
+                    let (sin_phi, cos_phi) = phi.sin_cos();
+                    let v =
+                        Vector3::spherical_direction_trig(sin_theta, cos_theta, sin_phi, cos_phi);
+
+                    assert_eq!(Vector3::spherical_direction(sin_theta, cos_theta, phi), v);
+                    assert!(v.abs_diff_eq(Vector3::from_spherical(theta, phi), 1e-15));
+                }
+            }
+        }
+
+        #[test]
+        fn spherical_direction_trig_clamps_theta() {
+            let v = Vector3::spherical_direction_trig(1.0 + 1e-7f32, -1e-7, 0.0, -1.0);
+
+            assert_eq!(Vector3::new(-1.0, 0.0, -1e-7), v);
+        }
+
+        #[test]
+        fn spherical_round_trip() {
+            use std::f64::consts::PI;
+
+            for i in 0..=8 {
+                let theta = PI * i as f64 / 8.0;
+                for j in 0..16 {
+                    let phi = 2.0 * PI * j as f64 / 16.0;
+                    let v = Vector3::spherical_direction(theta.sin(), theta.cos(), phi);
+
+                    assert!((v.length() - 1.0).abs() < 1e-12);
+                    assert!((v.spherical_theta() - theta).abs() < 1e-12);
+                    let phi_v = v.spherical_phi();
+                    assert!((0.0..2.0 * PI).contains(&phi_v));
+                    if i != 0 && i != 8 {
+                        assert!((phi_v - phi).abs() < 1e-12, "phi {} became {}", phi, phi_v);
+                    }
+
+                    let u = Vector3::spherical_direction(
+                        v.spherical_theta().sin(), v.spherical_theta().cos(), phi_v);
+                    assert!(u.abs_diff_eq(v, 1e-12), "{:?} became {:?}", v, u);
+                }
+            }
+        }
+
+        #[test]
+        fn spherical_phi_tiny_negative_y() {
+            use std::f64::consts::PI;
+
+            let phi = Vector3::new(1.0, -1e-300, 0.0).spherical_phi();
+            assert!((0.0..2.0 * PI).contains(&phi), "phi was {}", phi);
+            assert_eq!(0.0, phi);
+
+            let phi = Vector3::new(1.0f32, -1e-30, 0.0).spherical_phi();
+            assert!((0.0..2.0 * std::f32::consts::PI).contains(&phi), "phi was {}", phi);
+            assert_eq!(0.0, phi);
+
+            let phi = Vector3::new(1.0, -1e-10, 0.0).spherical_phi();
+            assert!(phi > 2.0 * PI - 1e-9 && phi < 2.0 * PI, "phi was {}", phi);
+        }
+
+        #[test]
+        fn spherical_angles_round_trip() {
+            use std::f64::consts::PI;
+
+            let directions = [
+                Vector3::new(1.0, 2.0, 2.0) / 3.0,
+                Vector3::new(-2.0, -1.0, 2.0) / 3.0,
+                Vector3::new(0.6, -0.8, 0.0),
+                Vector3::new(0.0, 0.6, -0.8),
+            ];
+            for &v in &directions {
+                let (theta, phi) = v.to_spherical();
+
+                assert!((0.0..=PI).contains(&theta));
+                assert!((0.0..2.0 * PI).contains(&phi));
+                assert!(Vector3::from_spherical(theta, phi).abs_diff_eq(v, 1e-15), "{:?}", v);
+            }
+        }
+
+        #[test]
+        fn spherical_angles_wrap_phi() {
+            use std::f64::consts::PI;
+
+            let v = Vector3::from_spherical(PI / 2.0, -PI / 2.0);
+            let (_, phi) = v.to_spherical();
+
+            assert!((phi - 3.0 * PI / 2.0).abs() < 1e-15);
+            assert!(Vector3::from_spherical(PI / 3.0, 2.0 * PI + 1.0)
+                .abs_diff_eq(Vector3::from_spherical(PI / 3.0, 1.0), 1e-15));
+        }
+
+        #[test]
+        fn spherical_angles_at_poles() {
+            use std::f64::consts::PI;
+
+            let (theta, _) = Vector3::new(0.0, 0.0, 1.0).to_spherical();
+            assert_eq!(0.0, theta);
+
+            let (theta, _) = Vector3::new(0.0, 0.0, -1.0).to_spherical();
+            assert_eq!(PI, theta);
+
+            // Any phi gives the same direction at the poles.
+            for &phi in &[0.0, 1.0, PI, 5.0] {
+                assert_eq!(Vector3::new(0.0, 0.0, 1.0), Vector3::from_spherical(0.0, phi));
+            }
+        }
+
+        #[test]
+        fn spherical_direction_in_frame() {
+            let n = Vector3::new(1.0, 2.0, 2.0) / 3.0;
+            let (x, y) = n.coordinate_system();
+            let v = Vector3::spherical_direction_in_frame(0.6, 0.8, 1.0, x, y, n);
+
+            assert!((v.length() - 1.0).abs() < 1e-12);
+            assert!((v.dot(n) - 0.8).abs() < 1e-12);
+            let v = Vector3::spherical_direction_in_frame(0.0, 1.0, 2.0, x, y, n);
+            assert!(v.abs_diff_eq(n, 1e-15));
+        }
+
+        #[test]
+        fn spherical_direction_clamps() {
+            let v = Vector3::spherical_direction(0.0, 1.0 + 1e-15, 0.0);
+
+            assert_eq!(Vector3::new(0.0, 0.0, 1.0), v);
+            assert_eq!(0.0, Vector3::new(0.0, 0.0, 1.0 + 1e-15).spherical_theta());
+        }
 
-impl<T: Num + Copy + ToPrimitive> Vector3<T> {
-    /// Computes the cross product of this vector with the given vector.
-    pub fn cross(self, other: Vector3<T>) -> Self {
-        return Self {
-            x: self.y * other.z - self.z * other.y,
-            y: self.z * other.x - self.x * other.z,
-            z: self.x * other.y - self.y * other.x,
-        };
-    }
+        #[test]
+        fn project_and_reject() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let w = Vector3::new(1.0, 2.0, -0.5);
+            let parallel = v.project_onto(w);
+            let perpendicular = v.reject_from(w);
 
-    // Should this actually return T?
-    /// Computes the squared length of this vector.
-    pub fn length_squared(self) -> f64 {
-        let length_squared = self.x * self.x + self.y * self.y + self.z * self.z;
-        return length_squared.to_f64().expect("Failed to convert to f64!");
-    }
+            assert!(v.abs_diff_eq(parallel + perpendicular, 1e-12));
+            assert!(parallel.cross(w).length() < 1e-12);
+            assert!(perpendicular.dot(w).abs() < 1e-12);
+        }
 
-    /// Computes the length of this vector.
-    pub fn length(self) -> f64 {
-        return self.length_squared().sqrt();
-    }
+        #[test]
+        fn project_onto_ignores_length_of_target() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let w = Vector3::new(1.0, 2.0, -0.5);
+            let parallel = v.project_onto(w);
 
-    /// Computes the normalized vector.
-    ///
-    /// A normalized vector is the vector with length 1 in the same direction as the original
-    /// vector.
-    pub fn normalize(self) -> Vector3<f64> {
-        let length = self.length();
-        return Vector3::new(
-            self.x.to_f64().unwrap() / length,
-            self.y.to_f64().unwrap() / length,
-            self.z.to_f64().unwrap() / length);
-    }
-}
+            assert!(parallel.abs_diff_eq(v.project_onto(w * -3.0), 1e-12));
+            assert!(parallel.abs_diff_eq(parallel.project_onto(w), 1e-12));
+            assert!(parallel.reject_from(w).abs_diff_eq(Vector3::zero(), 1e-12));
+        }
 
-impl<T: Signed> Vector3<T> {
-    /// Computes the absolute value of this vector.
-    pub fn abs(&self) -> Self {
-        Self {
-            x: self.x.abs(),
-            y: self.y.abs(),
-            z: self.z.abs(),
+        #[test]
+        fn gram_schmidt() {
+            let w = Vector3::new(1.0, 2.0, 2.0) / 3.0;
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let orthogonal = v.gram_schmidt(w);
+
+            assert!(orthogonal.dot(w).abs() < 1e-15);
+            assert!(orthogonal.abs_diff_eq(v.reject_from(w), 1e-15));
+
+            let v = Vector3::new(2.0, -1.0, 0.0);
+            assert!(v.dot(w).abs() < 1e-15);
+            assert!(v.gram_schmidt(w).abs_diff_eq(v, 1e-15));
         }
-    }
 
-    /// Computes the absolute value of the dot product of this and another vector.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use pbrust::vector3::Vector3;
-    ///
-    /// let v1 = Vector3::new(1, 2, 3);
-    /// let v2 = Vector3::new(-1, -2, -3);
-    ///
-    /// // absdot is a convenience method:
-    /// let absdot1 = v1.abs_dot(v2);
-    ///
-    /// // It's equivalent to:
-    /// let absdot2 = (v1.dot(v2) as i32).abs();
-    ///
-    /// assert_eq!(absdot1, absdot2);
-    /// ```
-    pub fn abs_dot(self, other: Vector3<T>) -> T {
-        return abs(self.dot(other));
-    }
-}
+        #[test]
+        fn gram_schmidt_parallel() {
+            let w = Vector3::new(1.0, 2.0, 2.0) / 3.0;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            assert!(w.gram_schmidt(w).abs_diff_eq(Vector3::zero(), 1e-15));
+            assert!((w * -4.0).gram_schmidt(w).abs_diff_eq(Vector3::zero(), 1e-15));
+        }
 
-    #[test]
-    fn same_vectors_are_equal() {
-        let v1 = Vector3::new(1, 2, 3);
-        let v2 = Vector3::new(1, 2, 3);
+        #[test]
+        fn gram_schmidt_pair() {
+            let n = Vector3::new(0.3, -1.2, 2.5);
+            let references = [
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(0.0, 1.0, 0.0),
+                Vector3::new(-2.0, 0.5, 1.0),
+                n + Vector3::new(1e-6, 0.0, 0.0),
+            ];
+            for &reference in &references {
+                let (a, b) = n.gram_schmidt_pair(reference).unwrap();
 
-        assert_eq!(v1, v2);
-    }
+                assert!((a.length() - 1.0).abs() < 1e-15);
+                assert!((b.length() - 1.0).abs() < 1e-15);
+                assert!(a.dot(b).abs() < 1e-15, "dot was {}", a.dot(b));
+                assert!(a.abs_diff_eq(n / n.length(), 1e-15));
+                // The second vector stays on the same side of the first as the reference.
+                assert!(b.dot(reference) > 0.0);
+            }
+        }
 
-    #[test]
-    fn different_vectors_are_not_equal() {
-        let v1 = Vector3::new(1, 2, 3);
-        let v2 = Vector3::new(-3, 0, 5);
+        #[test]
+        fn gram_schmidt_pair_degenerate() {
+            let v = Vector3::new(1.0, 2.0, 2.0);
+            let zero = Vector3::zero();
 
-        assert_ne!(v1, v2);
-    }
+            assert_eq!(None, v.gram_schmidt_pair(v));
+            assert_eq!(None, v.gram_schmidt_pair(v * -0.5));
+            assert_eq!(None, v.gram_schmidt_pair(v + Vector3::new(1e-12, 0.0, 0.0)));
+            assert_eq!(None, v.gram_schmidt_pair(zero));
+            assert_eq!(None, zero.gram_schmidt_pair(v));
 
-    mod math {
-        use super::*;
+            let v = Vector3::new(1.0f32, 0.0, 0.0);
+            assert_eq!(None, v.gram_schmidt_pair(Vector3::new(1.0, 1e-5, 0.0)));
+            assert!(v.gram_schmidt_pair(Vector3::new(1.0, 1e-3, 0.0)).is_some());
+        }
 
         #[test]
-        fn neg() {
-            let v = Vector3::new(-1, 2, -3);
-            let expected = Vector3::new(1, -2, 3);
+        #[cfg_attr(debug_assertions, should_panic(expected = "isn't normalized"))]
+        fn gram_schmidt_unnormalized() {
+            let v = Vector3::new(1.0, 2.0, 3.0);
 
-            assert_eq!(expected, -v);
+            v.gram_schmidt(Vector3::new(0.0, 0.0, 2.0));
         }
 
         #[test]
-        fn add_vectors() {
-            let v1 = Vector3::new(1, 2, 3);
-            let v2 = Vector3::new(-3, 0, 5);
-            let expected = Vector3::new(-2, 2, 8);
+        fn reject_from_axes() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
 
-            assert_eq!(expected, v1 + v2);
+            assert_eq!(Vector3::new(0.0, -1.2, 2.5), v.reject_from(Vector3::new(-4.0, 0.0, 0.0)));
+            assert_eq!(Vector3::new(0.3, 0.0, 2.5), v.reject_from(Vector3::new(0.0, 0.5, 0.0)));
+            assert_eq!(Vector3::new(0.3, -1.2, 0.0), v.reject_from(Vector3::new(0.0, 0.0, 1.0)));
         }
 
         #[test]
-        fn add_assign_vectors() {
-            let mut v1 = Vector3::new(1, 2, 3);
-            let v2 = Vector3::new(-3, 0, 5);
-            let expected = v1 + v2;
+        #[cfg_attr(debug_assertions, should_panic(expected = "Vector3 has NaN components"))]
+        fn project_onto_zero_vector() {
+            let v = Vector3::new(0.3, -1.2, 2.5);
+            let zero = Vector3::new(0.0, 0.0, 0.0);
 
-            v1 += v2;
+            let projected = v.project_onto(zero);
+            assert!(projected.x.is_nan() && projected.y.is_nan() && projected.z.is_nan());
 
-            assert_eq!(expected, v1);
+            let rejected = v.reject_from(zero);
+            assert!(rejected.x.is_nan() && rejected.y.is_nan() && rejected.z.is_nan());
         }
 
         #[test]
-        fn sub_vectors() {
-            let v1 = Vector3::new(1, 2, 3);
-            let v2 = Vector3::new(-3, 0, 5);
-            let expected = Vector3::new(4, 2, -2);
+        fn reflect_about_axes() {
+            let wo = Vector3::new(1.0, 2.0, 3.0);
 
-            assert_eq!(expected, v1 - v2);
+            assert_eq!(Vector3::new(1.0, -2.0, -3.0), wo.reflect(Vector3::new(1.0, 0.0, 0.0)));
+            assert_eq!(Vector3::new(-1.0, 2.0, -3.0), wo.reflect(Vector3::new(0.0, 1.0, 0.0)));
+            assert_eq!(Vector3::new(-1.0, -2.0, 3.0), wo.reflect(Vector3::new(0.0, 0.0, 1.0)));
         }
 
         #[test]
-        fn sub_assign_vectors() {
-            let mut v1 = Vector3::new(1, 2, 3);
-            let v2 = Vector3::new(-3, 0, 5);
-            let expected = Vector3::new(4, 2, -2);
+        fn reflect_45_degrees() {
+            let wo = Vector3::new(1.0, 0.0, 1.0).normalize();
+            let n = Vector3::new(0.0, 0.0, 1.0);
+            let wi = wo.reflect(n);
 
-            v1 -= v2;
+            assert!(wi.abs_diff_eq(Vector3::new(-1.0, 0.0, 1.0).normalize(), 1e-12));
+            assert!((wi.dot(n) - wo.dot(n)).abs() < 1e-12);
+        }
 
-            assert_eq!(expected, v1);
+        #[test]
+        fn reflect_grazing() {
+            let wo = Vector3::new(1.0, 1e-8, 0.0).normalize();
+            let n = Vector3::new(0.0, 1.0, 0.0);
+            let wi = wo.reflect(n);
+
+            assert!((wi.length() - 1.0).abs() < 1e-12);
+            assert!(wi.y > 0.0);
         }
 
         #[test]
-        fn mul_vector_and_scalar() {
-            let v = Vector3::new(1, 2, 3);
-            let expected = Vector3::new(-2, -4, -6);
+        fn reflect_twice() {
+            let wo = Vector3::new(0.3, -1.2, 2.5);
+            let n = Vector3::new(1.0, 2.0, -0.5).normalize();
 
-            assert_eq!(expected, v * -2);
+            assert!(wo.abs_diff_eq(wo.reflect(n).reflect(n), 1e-12));
+        }
 
-            let v = Vector3::new(1.0, 2.0, 3.0);
-            let expected = Vector3::new(-2.5, -5.0, -7.5);
+        #[test]
+        fn mirror() {
+            let d = Vector3::new(1.0, 2.0, 3.0);
 
-            assert_eq!(expected, v * -2.5);
+            assert_eq!(Vector3::new(-1.0, 2.0, 3.0), d.mirror(Vector3::new(1.0, 0.0, 0.0)));
+            let y = Vector3::new(0.0, 1.0, 0.0);
+            assert_eq!(-d.reflect(y), d.mirror(y));
         }
 
         #[test]
-        fn mul_assign_vector_and_scalar() {
-            let mut v = Vector3::new(1, 2, 3);
-            let expected = v * -2;
+        fn mirror_twice() {
+            let d = Vector3::new(0.3, -1.2, 2.5);
+            let n = Vector3::new(1.0, 2.0, -0.5).normalize();
 
-            v *= -2;
+            assert!(d.abs_diff_eq(d.mirror(n).mirror(n), 1e-12));
+        }
 
-            assert_eq!(expected, v);
+        #[test]
+        fn mirror_perpendicular() {
+            let d = Vector3::new(2.0, 0.0, -3.0);
+            let n = Vector3::new(0.0, 1.0, 0.0);
 
-            let mut v = Vector3::new(1.0, 2.0, 3.0);
-            let expected = v * -2.5;
+            assert_eq!(d, d.mirror(n));
+        }
 
-            v *= -2.5;
+        #[test]
+        fn refract_straight_through() {
+            let wi = Vector3::new(1.0, 2.0, 3.0).normalize();
+            let n = Vector3::new(0.0, 0.0, 1.0);
 
-            assert_eq!(expected, v);
+            let wt = wi.refract(n, 1.0).unwrap();
+
+            assert!(wt.abs_diff_eq(-wi, 1e-12));
         }
 
         #[test]
-        fn div_vector_and_scalar() {
-            let v = Vector3::new(-2, -4, -6);
-            let expected = Vector3::new(1, 2, 3);
+        fn refract_head_on() {
+            let n = Vector3::new(0.0, 1.0, 0.0);
 
-            assert_eq!(expected, v / -2);
+            assert_eq!(Some(-n), n.refract(n, 1.0 / 1.5));
+            assert_eq!(Some(-n), n.refract(n, 1.5));
+        }
 
-            let v = Vector3::new(-2.5, -5.0, -7.5);
-            let expected = Vector3::new(1.0, 2.0, 3.0);
+        #[test]
+        fn refract_30_degrees() {
+            let theta = 30f64.to_radians();
+            let wi = Vector3::new(theta.sin(), 0.0, theta.cos());
+            let n = Vector3::new(0.0, 0.0, 1.0);
 
-            assert_eq!(expected, v / -2.5);
+            // Air to glass: sin(theta_t) = sin(30) / 1.5 = 1/3.
+            let wt = wi.refract(n, 1.0 / 1.5).unwrap();
+            let expected = Vector3::new(-1.0 / 3.0, 0.0, -(8.0f64 / 9.0).sqrt());
+
+            assert!(wt.abs_diff_eq(expected, 1e-12));
+            assert!((wt.length() - 1.0).abs() < 1e-12);
         }
 
         #[test]
-        fn div_assign_vector_and_scalar() {
-            let mut v = Vector3::new(-2, -4, -6);
-            let expected = v / -2;
+        fn refract_total_internal_reflection() {
+            let n = Vector3::new(0.0, 0.0, 1.0);
 
-            v /= -2;
+            // Glass to air has a critical angle of about 41.8 degrees.
+            let theta = 60f64.to_radians();
+            let wi = Vector3::new(theta.sin(), 0.0, theta.cos());
 
-            assert_eq!(expected, v);
+            assert_eq!(None, wi.refract(n, 1.5));
 
-            let mut v = Vector3::new(-2.5, -5.0, -7.5);
-            let expected = v / -2.5;
+            let wi = Vector3::new(1.0, 0.0, 1e-3).normalize();
 
-            v /= -2.5;
+            assert_eq!(None, wi.refract(n, 1.5));
 
-            assert_eq!(expected, v);
+            let theta = 40f64.to_radians();
+            let wi = Vector3::new(theta.sin(), 0.0, theta.cos());
+
+            assert!(wi.refract(n, 1.5).is_some());
         }
 
         #[test]
-        fn dot_product() {
-            let v1 = Vector3::new(1, -2, -3);
-            let v2 = Vector3::new(2, 4, -6);
-            let expected = 1 * 2 + -2 * 4 + -3 * -6;
+        fn min_and_max() {
+            let v1 = Vector3::new(1, -5, 3);
+            let v2 = Vector3::new(2, -4, 3);
 
-            assert_eq!(expected, v1.dot(v2));
+            assert_eq!(Vector3::new(1, -5, 3), Vector3::min(v1, v2));
+            assert_eq!(Vector3::new(2, -4, 3), Vector3::max(v1, v2));
+
+            let v1 = Vector3::new(1.5, -5.0, 3.0);
+            let v2 = Vector3::new(-2.5, -4.0, 3.0);
+
+            assert_eq!(Vector3::new(-2.5, -5.0, 3.0), v1.min(v2));
+            assert_eq!(Vector3::new(1.5, -4.0, 3.0), v1.max(v2));
         }
 
         #[test]
-        fn cross_product() {
-            let v1 = Vector3::new(1, -2, -3);
-            let v2 = Vector3::new(2, 4, -6);
-            let expected = Vector3::new(-2 * -6 - -3 * 4, -3 * 2 - 1 * -6, 1 * 4 - -2 * 2);
+        fn min_and_max_expand_bounds() {
+            let points = [
+                Vector3::new(1.0, -2.0, 0.5),
+                Vector3::new(-3.0, 4.0, 0.0),
+                Vector3::new(2.0, 1.0, -6.0),
+            ];
+            let mut lower = points[0];
+            let mut upper = points[0];
+            for &p in &points[1..] {
+                lower = Vector3::min(lower, p);
+                upper = Vector3::max(upper, p);
+            }
 
-            assert_eq!(expected, v1.cross(v2));
+            assert_eq!(Vector3::new(-3.0, -2.0, -6.0), lower);
+            assert_eq!(Vector3::new(2.0, 4.0, 0.5), upper);
         }
 
         #[test]
-        fn length_squared() {
-            let v = Vector3::new(1, -2, -3);
-            let expected = (1 * 1 + -2 * -2 + -3 * -3) as f64;
+        fn min_and_max_ignore_nan() {
+            let v1 = Vector3 { x: f64::NAN, y: 1.0, z: f64::NAN };
+            let v2 = Vector3 { x: 2.0, y: f64::NAN, z: f64::NAN };
 
-            assert_eq!(expected, v.length_squared());
+            let min = v1.min(v2);
+            assert_eq!((2.0, 1.0), (min.x, min.y));
+            assert!(min.z.is_nan());
 
-            let v = Vector3::new(1.0, -2.0, -3.0);
-            let expected = (1 * 1 + -2 * -2 + -3 * -3) as f64;
+            let max = v1.max(v2);
+            assert_eq!((2.0, 1.0), (max.x, max.y));
+            assert!(max.z.is_nan());
+        }
 
-            assert_eq!(expected, v.length_squared());
+        #[test]
+        fn min_and_max_component() {
+            let v = Vector3::new(-1, -7, 4);
+
+            assert_eq!(-7, v.min_component());
+            assert_eq!(4, v.max_component());
+
+            let v = Vector3 { x: 2.0, y: f64::NAN, z: -3.0 };
+
+            assert_eq!(-3.0, v.min_component());
+            assert_eq!(2.0, v.max_component());
         }
 
         #[test]
-        fn length() {
-            let v = Vector3::new(1, -2, -3);
-            let expected = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
+        fn min_and_max_component_ties() {
+            let v = Vector3::new(-2, 5, -2);
 
-            assert_eq!(expected, v.length());
+            assert_eq!(-2, v.min_component());
+            assert_eq!(5, v.max_component());
 
-            let v = Vector3::new(1.0, -2.0, -3.0);
-            let expected = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
+            let v = Vector3::new(4, 4, 4);
 
-            assert_eq!(expected, v.length());
+            assert_eq!(4, v.min_component());
+            assert_eq!(4, v.max_component());
         }
 
         #[test]
-        fn normalize() {
-            let v = Vector3::new(1, -2, -3);
-            let length = ((1 * 1 + -2 * -2 + -3 * -3) as f64).sqrt();
-            let expected = Vector3::new(1.0 / length, -2.0 / length, -3.0 / length);
+        fn min_dimension() {
+            assert_eq!(0, Vector3::new(-3, -1, 2).min_dimension());
+            assert_eq!(1, Vector3::new(3, -1, 2).min_dimension());
+            assert_eq!(2, Vector3::new(0.5, 1.5, -2.5).min_dimension());
+        }
 
-            assert_eq!(expected, v.normalize());
+        #[test]
+        fn min_dimension_ties() {
+            assert_eq!(1, Vector3::new(-3, -3, 2).min_dimension());
+            assert_eq!(2, Vector3::new(-3, 1, -3).min_dimension());
+            assert_eq!(2, Vector3::new(3, 3, 3).min_dimension());
+        }
+
+        #[test]
+        fn clamp() {
+            let v = Vector3::new(-2.5, 0.5, 7.0);
+            let min = Vector3::new(-1.0, 0.0, 0.0);
+            let max = Vector3::new(1.0, 1.0, 5.0);
+
+            assert_eq!(Vector3::new(-1.0, 0.5, 5.0), v.clamp(min, max));
+        }
+
+        #[test]
+        fn clamp_below_inside_and_above() {
+            let min = Vector3::new(-1, 0, 2);
+            let max = Vector3::new(1, 4, 2);
+
+            assert_eq!(min, Vector3::new(-5, -1, 0).clamp(min, max));
+            assert_eq!(Vector3::new(0, 3, 2), Vector3::new(0, 3, 2).clamp(min, max));
+            assert_eq!(max, Vector3::new(5, 9, 3).clamp(min, max));
+
+            let lo = Vector3::new(-3.0, -1.0, -0.6);
+            let mid = Vector3::new(0.25, -0.5, 0.5);
+            let hi = Vector3::new(3.0, 1.0, 0.6);
+
+            assert_eq!(Vector3::new(-0.5, -0.5, -0.5), lo.clamp_scalar(-0.5, 0.5));
+            assert_eq!(mid, mid.clamp_scalar(-0.5, 0.5));
+            assert_eq!(Vector3::new(0.5, 0.5, 0.5), hi.clamp_scalar(-0.5, 0.5));
+        }
+
+        #[test]
+        #[cfg_attr(
+            debug_assertions,
+            should_panic(expected = "Lower bound is greater than upper bound")
+        )]
+        fn clamp_crossed_bounds() {
+            let v = Vector3::new(-2, 2, 7);
+            let min = Vector3::new(1, 1, 1);
+            let max = Vector3::new(0, 0, 0);
+
+            assert_eq!(min, v.clamp(min, max));
+        }
+
+        #[test]
+        fn clamp_nan() {
+            let v = Vector3 { x: f64::NAN, y: 2.0, z: -2.0 }.clamp_scalar(-1.0, 1.0);
+
+            assert!(v.x.is_nan());
+            assert_eq!((1.0, -1.0), (v.y, v.z));
+        }
+
+        #[test]
+        fn all_predicates() {
+            let v = Vector3::new(1, 2, 3);
+
+            assert!(v.all_lt(Vector3::new(2, 3, 4)));
+            assert!(!v.all_lt(Vector3::new(2, 2, 4)));
+            assert!(v.all_le(Vector3::new(2, 2, 4)));
+            assert!(!v.all_le(Vector3::new(2, 1, 4)));
+            assert!(v.all_gt(Vector3::new(0, 1, 2)));
+            assert!(!v.all_gt(Vector3::new(0, 1, 3)));
+            assert!(v.all_ge(Vector3::new(0, 1, 3)));
+            assert!(!v.all_ge(Vector3::new(0, 1, 4)));
+            assert!(v.all_le(v) && v.all_ge(v) && !v.all_lt(v) && !v.all_gt(v));
+        }
+
+        #[test]
+        fn any_predicates() {
+            let v = Vector3::new(1.0, 2.0, 3.0);
+
+            assert!(v.any_lt(Vector3::new(0.0, 0.0, 4.0)));
+            assert!(!v.any_lt(Vector3::new(0.0, 2.0, 3.0)));
+            assert!(v.any_le(Vector3::new(0.0, 2.0, 0.0)));
+            assert!(!v.any_le(Vector3::new(0.0, 1.0, 2.0)));
+            assert!(v.any_gt(Vector3::new(5.0, 5.0, 2.0)));
+            assert!(!v.any_gt(Vector3::new(1.0, 2.0, 3.0)));
+            assert!(v.any_ge(Vector3::new(5.0, 2.0, 5.0)));
+            assert!(!v.any_ge(Vector3::new(5.0, 5.0, 5.0)));
+        }
+
+        #[test]
+        fn comparison_predicates_with_nan() {
+            let nan = Vector3 { x: 0.0, y: f64::NAN, z: 0.0 };
+            let v = Vector3::new(-1.0, -1.0, -1.0);
+
+            assert!(!nan.all_gt(v) && !nan.all_ge(v) && !v.all_lt(nan) && !v.all_le(nan));
+            assert!(!nan.all_lt(nan) && !nan.all_le(nan) && !nan.all_ge(nan));
+
+            let nan_only = Vector3 { x: f64::NAN, y: f64::NAN, z: f64::NAN };
+            assert!(!nan_only.any_lt(v) && !nan_only.any_le(v));
+            assert!(!nan_only.any_gt(v) && !nan_only.any_ge(v));
+            assert!(nan.any_gt(v));
+        }
+
+        #[test]
+        fn clamp_scalar() {
+            let v = Vector3::new(-2, 0, 7);
+
+            assert_eq!(Vector3::new(-1, 0, 5), v.clamp_scalar(-1, 5));
+        }
+
+        #[test]
+        fn max_dimension() {
+            assert_eq!(0, Vector3::new(3, -1, 2).max_dimension());
+            assert_eq!(1, Vector3::new(-3, -1, -2).max_dimension());
+            assert_eq!(2, Vector3::new(0.5, 1.5, 2.5).max_dimension());
+        }
+
+        #[test]
+        fn max_dimension_ties() {
+            assert_eq!(1, Vector3::new(3, 3, 2).max_dimension());
+            assert_eq!(2, Vector3::new(3, 1, 3).max_dimension());
+            assert_eq!(2, Vector3::new(3, 3, 3).max_dimension());
         }
     }
 }
\ No newline at end of file
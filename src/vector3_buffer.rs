@@ -0,0 +1,315 @@
+use crate::vector3::Vector3;
+use core::iter::FromIterator;
+use num::{Float, Num};
+
+/// A growable list of vectors stored as a structure of arrays.
+///
+/// The `x`, `y`, and `z` components are kept in three separate `Vec`s rather than one `Vec` of
+/// `Vector3`s. Batch operations then stream through contiguous arrays of a single component
+/// type, which uses memory bandwidth better and is easier for the compiler to vectorize when
+/// processing millions of vectors at once.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Vector3Buffer<T: Num> {
+    x: Vec<T>,
+    y: Vec<T>,
+    z: Vec<T>,
+}
+
+impl<T: Num + Copy> Vector3Buffer<T> {
+    /// Creates an empty buffer.
+    pub fn new() -> Vector3Buffer<T> {
+        Vector3Buffer { x: Vec::new(), y: Vec::new(), z: Vec::new() }
+    }
+
+    /// Creates an empty buffer with room for at least `capacity` vectors.
+    pub fn with_capacity(capacity: usize) -> Vector3Buffer<T> {
+        return Vector3Buffer {
+            x: Vec::with_capacity(capacity),
+            y: Vec::with_capacity(capacity),
+            z: Vec::with_capacity(capacity),
+        };
+    }
+
+    /// Creates a buffer holding copies of the given vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector3_buffer::Vector3Buffer;
+    ///
+    /// let vectors = [Vector3::new(1, 2, 3), Vector3::new(4, 5, 6)];
+    /// let buffer = Vector3Buffer::from_slice(&vectors);
+    ///
+    /// assert_eq!(&[1, 4], buffer.xs());
+    /// assert_eq!(vectors.to_vec(), buffer.to_vec());
+    /// ```
+    pub fn from_slice(vectors: &[Vector3<T>]) -> Vector3Buffer<T> {
+        let mut buffer = Vector3Buffer::with_capacity(vectors.len());
+        for &v in vectors {
+            buffer.push(v);
+        }
+        return buffer;
+    }
+
+    /// Copies the vectors in this buffer into a `Vec`.
+    pub fn to_vec(&self) -> Vec<Vector3<T>> {
+        return self.iter().collect();
+    }
+
+    /// Appends a vector to the end of this buffer.
+    pub fn push(&mut self, v: Vector3<T>) {
+        self.x.push(v.x);
+        self.y.push(v.y);
+        self.z.push(v.z);
+    }
+
+    /// Gets the number of vectors in this buffer.
+    pub fn len(&self) -> usize {
+        return self.x.len();
+    }
+
+    /// Checks whether this buffer has no vectors.
+    pub fn is_empty(&self) -> bool {
+        return self.x.is_empty();
+    }
+
+    /// Gets the vector at the given index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Vector3<T>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(Vector3::new(self.x[index], self.y[index], self.z[index]));
+    }
+
+    /// Replaces the vector at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, v: Vector3<T>) {
+        self.x[index] = v.x;
+        self.y[index] = v.y;
+        self.z[index] = v.z;
+    }
+
+    /// Gets the `x` components of all the vectors in this buffer.
+    pub fn xs(&self) -> &[T] {
+        return &self.x;
+    }
+
+    /// Gets the `y` components of all the vectors in this buffer.
+    pub fn ys(&self) -> &[T] {
+        return &self.y;
+    }
+
+    /// Gets the `z` components of all the vectors in this buffer.
+    pub fn zs(&self) -> &[T] {
+        return &self.z;
+    }
+
+    /// Iterates over copies of the vectors in this buffer.
+    pub fn iter(&self) -> impl Iterator<Item = Vector3<T>> + '_ {
+        return (0..self.len()).map(move |i| Vector3::new(self.x[i], self.y[i], self.z[i]));
+    }
+
+    /// Adds the given vector to every vector in this buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector3_buffer::Vector3Buffer;
+    ///
+    /// let mut buffer = Vector3Buffer::from_slice(&[Vector3::new(1, 2, 3), Vector3::new(0, 0, 0)]);
+    /// buffer.add_assign(Vector3::new(1, -1, 2));
+    ///
+    /// assert_eq!(vec![Vector3::new(2, 1, 5), Vector3::new(1, -1, 2)], buffer.to_vec());
+    /// ```
+    pub fn add_assign(&mut self, v: Vector3<T>) {
+        add_to_all(&mut self.x, v.x);
+        add_to_all(&mut self.y, v.y);
+        add_to_all(&mut self.z, v.z);
+    }
+
+    /// Multiplies every vector in this buffer by the given scalar.
+    pub fn scale(&mut self, s: T) {
+        for c in self.x.iter_mut().chain(&mut self.y).chain(&mut self.z) {
+            *c = *c * s;
+        }
+    }
+
+    /// Computes the dot product of every vector in this buffer with the given vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector3_buffer::Vector3Buffer;
+    ///
+    /// let buffer = Vector3Buffer::from_slice(&[Vector3::new(1, 2, 3), Vector3::new(4, 5, 6)]);
+    ///
+    /// assert_eq!(vec![3, 6], buffer.dot_all(Vector3::new(0, 0, 1)));
+    /// ```
+    pub fn dot_all(&self, v: Vector3<T>) -> Vec<T> {
+        let xy = self.x.iter().zip(&self.y);
+        return xy.zip(&self.z).map(|((&x, &y), &z)| x * v.x + y * v.y + z * v.z).collect();
+    }
+}
+
+impl<T: Float> Vector3Buffer<T> {
+    /// Scales every vector in this buffer to length 1.
+    ///
+    /// Vectors of length 0 have no direction, so they're left as zero, like
+    /// [`Vector3::normalize_or_zero`].
+    pub fn normalize_all(&mut self) {
+        for i in 0..self.len() {
+            let length = (self.x[i] * self.x[i] + self.y[i] * self.y[i] + self.z[i] * self.z[i])
+                .sqrt();
+            if length.is_zero() {
+                continue;
+            }
+            self.x[i] = self.x[i] / length;
+            self.y[i] = self.y[i] / length;
+            self.z[i] = self.z[i] / length;
+        }
+    }
+}
+
+impl<T: Num + Copy> FromIterator<Vector3<T>> for Vector3Buffer<T> {
+    fn from_iter<I: IntoIterator<Item = Vector3<T>>>(iter: I) -> Self {
+        let mut buffer = Vector3Buffer::new();
+        for v in iter {
+            buffer.push(v);
+        }
+        return buffer;
+    }
+}
+
+impl<T: Num + Copy> From<&[Vector3<T>]> for Vector3Buffer<T> {
+    fn from(vectors: &[Vector3<T>]) -> Self {
+        return Vector3Buffer::from_slice(vectors);
+    }
+}
+
+/// Adds `value` to every element of `components`.
+fn add_to_all<T: Num + Copy>(components: &mut [T], value: T) {
+    for c in components {
+        *c = *c + value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Generates vectors from a seeded random number generator, so failures reproduce.
+    fn random_vectors(count: usize, seed: u64) -> Vec<Vector3<f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut next = move || rng.gen_range(-100.0..100.0);
+        return (0..count).map(|_| Vector3::new(next(), next(), next())).collect();
+    }
+
+    #[test]
+    fn round_trip() {
+        let vectors = random_vectors(100, 1);
+        let buffer = Vector3Buffer::from_slice(&vectors);
+
+        assert_eq!(100, buffer.len());
+        assert_eq!(vectors, buffer.to_vec());
+        assert_eq!(buffer, vectors.iter().copied().collect());
+        assert_eq!(buffer, Vector3Buffer::from(&vectors[..]));
+        assert_eq!(vectors.iter().map(|v| v.y).collect::<Vec<_>>(), buffer.ys());
+    }
+
+    #[test]
+    fn empty() {
+        let buffer = Vector3Buffer::<f32>::new();
+
+        assert!(buffer.is_empty());
+        assert_eq!(None, buffer.get(0));
+        assert_eq!(Vec::<Vector3<f32>>::new(), buffer.to_vec());
+        assert_eq!(Vector3Buffer::default(), buffer);
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut buffer = Vector3Buffer::from_slice(&[Vector3::new(1, 2, 3), Vector3::new(4, 5, 6)]);
+        buffer.set(0, Vector3::new(-1, -2, -3));
+
+        assert_eq!(Some(Vector3::new(-1, -2, -3)), buffer.get(0));
+        assert_eq!(Some(Vector3::new(4, 5, 6)), buffer.get(1));
+        assert_eq!(None, buffer.get(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        Vector3Buffer::from_slice(&[Vector3::new(1, 2, 3)]).set(1, Vector3::new(0, 0, 0));
+    }
+
+    mod math {
+        use super::*;
+
+        #[test]
+        fn add_assign_matches_vector3() {
+            let vectors = random_vectors(1000, 2);
+            let offset = Vector3::new(0.25, -3.5, 12.0);
+            let mut buffer = Vector3Buffer::from_slice(&vectors);
+            buffer.add_assign(offset);
+
+            let expected: Vec<_> = vectors.iter().map(|&v| v + offset).collect();
+            assert_eq!(expected, buffer.to_vec());
+        }
+
+        #[test]
+        fn scale_matches_vector3() {
+            let vectors = random_vectors(1000, 3);
+            let mut buffer = Vector3Buffer::from_slice(&vectors);
+            buffer.scale(-0.3);
+
+            let expected: Vec<_> = vectors.iter().map(|&v| v * -0.3).collect();
+            assert_eq!(expected, buffer.to_vec());
+        }
+
+        #[test]
+        fn dot_all_matches_vector3() {
+            let vectors = random_vectors(1000, 4);
+            let v = Vector3::new(1.5, -0.5, 2.0);
+            let buffer = Vector3Buffer::from_slice(&vectors);
+
+            let expected: Vec<_> = vectors.iter().map(|u| u.dot(v)).collect();
+            assert_eq!(expected, buffer.dot_all(v));
+        }
+
+        #[test]
+        fn normalize_all_matches_vector3() {
+            let vectors = random_vectors(1000, 5);
+            let mut buffer = Vector3Buffer::from_slice(&vectors);
+            buffer.normalize_all();
+
+            for (v, normalized) in vectors.iter().zip(buffer.iter()) {
+                assert_eq!(v.normalize(), normalized);
+            }
+        }
+
+        #[test]
+        fn normalize_all_f32() {
+            let mut buffer = Vector3Buffer::from_slice(&[Vector3::new(3.0f32, 0.0, -4.0)]);
+            buffer.normalize_all();
+
+            assert_eq!(Some(Vector3::new(0.6, 0.0, -0.8)), buffer.get(0));
+        }
+
+        #[test]
+        fn normalize_all_zero() {
+            let zero = Vector3::new(0.0, 0.0, 0.0);
+            let mut buffer = Vector3Buffer::from_slice(&[zero, Vector3::new(0.0, 2.0, 0.0)]);
+            buffer.normalize_all();
+
+            assert_eq!(vec![zero, Vector3::new(0.0, 1.0, 0.0)], buffer.to_vec());
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::matrix4x4::Matrix4x4;
+use crate::vector3::Vector3;
+use derive_more::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use num::{Float, Num, ToPrimitive};
+use core::ops;
+
+/// A 4D vector, mostly used for the homogeneous coordinates of points and vectors in 3D space.
+///
+/// Points have a `w` of 1 and vectors have a `w` of 0, so transforming either by a `Matrix4x4`
+/// applies translation to points but not to vectors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Neg)]
+#[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
+pub struct Vector4<T: Num> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T: Num> Vector4<T> {
+    /// Creates a new vector.
+    pub fn new(x: T, y: T, z: T, w: T) -> Vector4<T> {
+        Vector4 { x, y, z, w }
+    }
+
+    /// Creates the homogeneous coordinates of the point at the given position, with a `w` of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector4::Vector4;
+    ///
+    /// assert_eq!(Vector4::new(1, 2, 3, 1), Vector4::from_point(Vector3::new(1, 2, 3)));
+    /// ```
+    pub fn from_point(p: Vector3<T>) -> Vector4<T> {
+        return Vector4::new(p.x, p.y, p.z, T::one());
+    }
+
+    /// Creates the homogeneous coordinates of the given vector, with a `w` of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector4::Vector4;
+    ///
+    /// assert_eq!(Vector4::new(1, 2, 3, 0), Vector4::from_vector(Vector3::new(1, 2, 3)));
+    /// ```
+    pub fn from_vector(v: Vector3<T>) -> Vector4<T> {
+        return Vector4::new(v.x, v.y, v.z, T::zero());
+    }
+
+    /// Computes the dot product of this vector with the given vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector4::Vector4;
+    ///
+    /// let v1 = Vector4::new(1, 2, 3, 4);
+    /// let v2 = Vector4::new(5, 6, 7, 8);
+    /// let expected = 1*5 + 2*6 + 3*7 + 4*8;
+    ///
+    /// assert_eq!(expected, v1.dot(v2));
+    /// ```
+    pub fn dot(self, other: Vector4<T>) -> T {
+        return self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w;
+    }
+}
+
+impl<T: Num + Copy> Vector4<T> {
+    /// Computes the squared length of this vector.
+    pub fn length_squared(self) -> T {
+        return self.dot(self);
+    }
+}
+
+impl<T: Num + Copy + ToPrimitive> Vector4<T> {
+    /// Converts these homogeneous coordinates to a position in 3D space by dividing `x`, `y`,
+    /// and `z` by `w`.
+    ///
+    /// Returns `None` if `w` is 0, since the coordinates of vectors can't be projected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pbrust::vector3::Vector3;
+    /// use pbrust::vector4::Vector4;
+    ///
+    /// let v = Vector4::new(2, -4, 6, 2);
+    ///
+    /// assert_eq!(Some(Vector3::new(1.0, -2.0, 3.0)), v.project());
+    /// assert_eq!(None, Vector4::new(2, -4, 6, 0).project());
+    /// ```
+    pub fn project(self) -> Option<Vector3<f64>> {
+        let to_f64 = |c: T| c.to_f64().expect("Failed to convert to f64!");
+        let w = to_f64(self.w);
+        if w == 0.0 {
+            return None;
+        }
+        return Some(Vector3::new(to_f64(self.x) / w, to_f64(self.y) / w, to_f64(self.z) / w));
+    }
+}
+
+impl<T: Float> Vector4<T> {
+    /// Computes the length of this vector.
+    pub fn length(self) -> T {
+        return self.length_squared().sqrt();
+    }
+}
+
+impl ops::Mul<Vector4<f64>> for Matrix4x4 {
+    type Output = Vector4<f64>;
+
+    fn mul(self, v: Vector4<f64>) -> Vector4<f64> {
+        let row = |i: usize| Vector4::new(self.m[i][0], self.m[i][1], self.m[i][2], self.m[i][3]);
+        return Vector4::new(row(0).dot(v), row(1).dot(v), row(2).dot(v), row(3).dot(v));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_and_vectors() {
+        let v = Vector3::new(1.5, -2.0, 3.0);
+
+        assert_eq!(1.0, Vector4::from_point(v).w);
+        assert_eq!(0.0, Vector4::from_vector(v).w);
+        assert_eq!(Some(v), Vector4::from_point(v).project());
+    }
+
+    #[test]
+    fn project_zero_w() {
+        let v = Vector3::new(0.0, 1.0, 2.0);
+
+        assert_eq!(None, Vector4::from_vector(v).project());
+    }
+
+    mod math {
+        use super::*;
+
+        fn translation() -> Matrix4x4 {
+            return Matrix4x4::new([
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 2.0],
+                [0.0, 0.0, 1.0, 3.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]);
+        }
+
+        #[test]
+        fn ops() {
+            let v1 = Vector4::new(1, 2, 3, 4);
+            let v2 = Vector4::new(-3, 0, 1, 1);
+
+            assert_eq!(Vector4::new(-2, 2, 4, 5), v1 + v2);
+            assert_eq!(Vector4::new(4, 2, 2, 3), v1 - v2);
+            assert_eq!(Vector4::new(2, 4, 6, 8), v1 * 2);
+            assert_eq!(Vector4::new(-1, -2, -3, -4), -v1);
+        }
+
+        #[test]
+        fn dot_and_length() {
+            let v = Vector4::new(1.0, -2.0, 2.0, 4.0);
+
+            assert_eq!(1.0 * 3.0 + -2.0 * 1.0, v.dot(Vector4::new(3.0, 1.0, 0.0, 0.0)));
+            assert_eq!(25.0, v.length_squared());
+            assert_eq!(5.0, v.length());
+        }
+
+        #[test]
+        fn translation_moves_points_but_not_vectors() {
+            let v = Vector3::new(1.0, 1.0, 1.0);
+
+            let p = translation() * Vector4::from_point(v);
+            assert_eq!(Vector4::new(2.0, 3.0, 4.0, 1.0), p);
+
+            let d = translation() * Vector4::from_vector(v);
+            assert_eq!(Vector4::from_vector(v), d);
+        }
+
+        #[test]
+        fn perspective_divide() {
+            // Maps z to w, so points farther away shrink towards the origin.
+            let perspective = Matrix4x4::new([
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ]);
+            let near = perspective * Vector4::from_point(Vector3::new(2.0, -1.0, 1.0));
+            let far = perspective * Vector4::from_point(Vector3::new(2.0, -1.0, 4.0));
+
+            assert_eq!(Some(Vector3::new(2.0, -1.0, 1.0)), near.project());
+            assert_eq!(Some(Vector3::new(0.5, -0.25, 1.0)), far.project());
+        }
+    }
+}